@@ -0,0 +1,136 @@
+// Copyright 2021 Shin Yoshida
+//
+// "LGPL-3.0-or-later OR Apache-2.0 OR BSD-2-Clause"
+//
+// This is part of mouse-leveldb
+//
+//  mouse-leveldb is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU Lesser General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  mouse-leveldb is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU Lesser General Public License for more details.
+//
+//  You should have received a copy of the GNU Lesser General Public License
+//  along with mouse-leveldb.  If not, see <http://www.gnu.org/licenses/>.
+//
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted
+// provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of
+//    conditions and the following disclaimer.
+// 2. Redistributions in binary form must reproduce the above copyright notice, this
+//    list of conditions and the following disclaimer in the documentation and/or other
+//    materials provided with the distribution.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+// ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+// WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+// IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT,
+// INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+// NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+
+use crate::database::Database;
+use crate::error::{self, Result};
+use crate::write_batch::WriteBatch;
+use std::io;
+use std::sync::Arc;
+use tokio::task::{self, JoinError};
+
+/// Same to [`crate::get`] except for running on the blocking thread pool of `tokio` , and
+/// returning the value in a `Vec` .
+///
+/// This is available only if feature `async` is enabled. The returned future must be polled in
+/// the `tokio` runtime.
+///
+/// It is an error if `db` is not opened. Returns an empty `Vec` if `key` is not found.
+///
+/// # Examples
+///
+/// ```
+/// use mouse_leveldb::{Database, WriteBatch};
+/// use std::ffi::CString;
+/// use std::sync::Arc;
+/// use tempfile;
+///
+/// let tmp = tempfile::tempdir().unwrap();
+/// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+///
+/// let mut db = Database::new();
+/// db.open(&path).unwrap();
+/// let db = Arc::new(db);
+///
+/// let rt = tokio::runtime::Builder::new_current_thread().build().unwrap();
+/// rt.block_on(async {
+///     let mut batch = WriteBatch::new();
+///     batch.put(b"key", b"value");
+///     mouse_leveldb::write_async(db.clone(), batch).await.unwrap();
+///
+///     let value = mouse_leveldb::get_async(db.clone(), b"key".to_vec()).await.unwrap();
+///     assert_eq!(b"value".to_vec(), value);
+///
+///     let value = mouse_leveldb::get_async(db.clone(), b"missing".to_vec()).await.unwrap();
+///     assert_eq!(true, value.is_empty());
+/// });
+/// ```
+pub async fn get_async(db: Arc<Database>, key: Vec<u8>) -> Result<Vec<u8>> {
+    let f = move || crate::get(&db, &key).map(|octets| octets.into_vec());
+    task::spawn_blocking(f).await.unwrap_or_else(join_error_)
+}
+
+/// Same to [`crate::write`] except for running on the blocking thread pool of `tokio` .
+///
+/// This is available only if feature `async` is enabled. The returned future must be polled in
+/// the `tokio` runtime.
+///
+/// `batch` is dropped after written. It is an error if `db` is not opened.
+///
+/// # Examples
+///
+/// ```
+/// use mouse_leveldb::{Database, ErrorKind, WriteBatch};
+/// use std::sync::Arc;
+///
+/// let db = Arc::new(Database::new());
+///
+/// let rt = tokio::runtime::Builder::new_current_thread().build().unwrap();
+/// rt.block_on(async {
+///     let mut batch = WriteBatch::new();
+///     batch.put(b"key", b"value");
+///     let e = mouse_leveldb::write_async(db, batch).await.unwrap_err();
+///     assert_eq!(ErrorKind::NotOpened, e.kind());
+/// });
+/// ```
+pub async fn write_async(db: Arc<Database>, mut batch: WriteBatch) -> Result<()> {
+    let f = move || crate::write(&db, &mut batch);
+    task::spawn_blocking(f).await.unwrap_or_else(join_error_)
+}
+
+/// Resumes the panic of the task, or converts the cancellation into an error.
+fn join_error_<T>(e: JoinError) -> Result<T> {
+    if e.is_panic() {
+        std::panic::resume_unwind(e.into_panic());
+    }
+    Err(error::io(io::Error::new(io::ErrorKind::Interrupted, e)))
+}
@@ -0,0 +1,168 @@
+// Copyright 2021 Shin Yoshida
+//
+// "LGPL-3.0-or-later OR Apache-2.0 OR BSD-2-Clause"
+//
+// This is part of mouse-leveldb
+//
+//  mouse-leveldb is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU Lesser General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  mouse-leveldb is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU Lesser General Public License for more details.
+//
+//  You should have received a copy of the GNU Lesser General Public License
+//  along with mouse-leveldb.  If not, see <http://www.gnu.org/licenses/>.
+//
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted
+// provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of
+//    conditions and the following disclaimer.
+// 2. Redistributions in binary form must reproduce the above copyright notice, this
+//    list of conditions and the following disclaimer in the documentation and/or other
+//    materials provided with the distribution.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+// ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+// WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+// IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT,
+// INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+// NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+
+use crate::database::Database;
+use crate::error::Result;
+use crate::test_db::TestDb;
+use crate::write_batch::WriteBatch;
+use core::ops::{Deref, DerefMut};
+use std::path::Path;
+
+/// The number of the (key, value) pairs written at once.
+const BATCH_LEN: usize = 1000;
+
+/// `BenchmarkDb` is a [`Database`] opened in a new temporary directory to fill with generated
+/// data for load testing.
+///
+/// This is available only if feature `testing` is enabled.
+///
+/// User can access the wrapped [`Database`] via the `Deref` and `DerefMut` implementation.
+///
+/// # Examples
+///
+/// ```
+/// use mouse_leveldb::BenchmarkDb;
+///
+/// let db = BenchmarkDb::new().unwrap();
+///
+/// db.fill_sequential(100, 16, 100).unwrap();
+/// assert_eq!(100, db.count().unwrap());
+///
+/// let (key, value) = db.first_key_value().unwrap().unwrap();
+/// assert_eq!(16, key.len());
+/// assert_eq!(100, value.len());
+///
+/// db.fill_random(100, 16, 100).unwrap();
+/// assert!(100 < db.count().unwrap());
+/// ```
+pub struct BenchmarkDb {
+    db: TestDb,
+}
+
+impl BenchmarkDb {
+    /// Creates a new temporary directory and opens a new empty database there.
+    pub fn new() -> Result<Self> {
+        TestDb::new().map(|db| Self { db })
+    }
+
+    /// Returns the path to the temporary directory where the database files are stored.
+    #[inline]
+    pub fn dir(&self) -> &Path {
+        self.db.dir()
+    }
+
+    /// Writes `n` (key, value) pairs whose keys are sequential.
+    ///
+    /// The key is the big-endian index `0..n` padded with zeros (or truncated) to `key_size`
+    /// bytes, and the value is `val_size` bytes.
+    pub fn fill_sequential(&self, n: usize, key_size: usize, val_size: usize) -> Result<()> {
+        self.fill_(n, key_size, val_size, |i| i as u64)
+    }
+
+    /// Writes `n` (key, value) pairs whose keys are pseudo random.
+    ///
+    /// The key is a pseudo random number padded with zeros (or truncated) to `key_size` bytes, and
+    /// the value is `val_size` bytes. The same keys are generated on every call, and they can
+    /// be duplicated.
+    pub fn fill_random(&self, n: usize, key_size: usize, val_size: usize) -> Result<()> {
+        // xorshift64
+        let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+        self.fill_(n, key_size, val_size, move |_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        })
+    }
+
+    fn fill_<F>(&self, n: usize, key_size: usize, val_size: usize, mut f: F) -> Result<()>
+    where
+        F: FnMut(usize) -> u64,
+    {
+        let value: Vec<u8> = (0..val_size).map(|i| i as u8).collect();
+        let mut key = vec![0; key_size];
+        let mut batch = WriteBatch::new();
+
+        for i in 0..n {
+            let bytes = f(i).to_be_bytes();
+            if key_size < bytes.len() {
+                key.copy_from_slice(&bytes[bytes.len() - key_size..]);
+            } else {
+                key[key_size - bytes.len()..].copy_from_slice(&bytes);
+            }
+            batch.put(&key, &value);
+
+            if batch.len() == BATCH_LEN {
+                crate::write(&self.db, &mut batch)?;
+            }
+        }
+
+        crate::write(&self.db, &mut batch)
+    }
+}
+
+impl Deref for BenchmarkDb {
+    type Target = Database;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.db
+    }
+}
+
+impl DerefMut for BenchmarkDb {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.db
+    }
+}
@@ -0,0 +1,151 @@
+// Copyright 2021 Shin Yoshida
+//
+// "LGPL-3.0-or-later OR Apache-2.0 OR BSD-2-Clause"
+//
+// This is part of mouse-leveldb
+//
+//  mouse-leveldb is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU Lesser General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  mouse-leveldb is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU Lesser General Public License for more details.
+//
+//  You should have received a copy of the GNU Lesser General Public License
+//  along with mouse-leveldb.  If not, see <http://www.gnu.org/licenses/>.
+//
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted
+// provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of
+//    conditions and the following disclaimer.
+// 2. Redistributions in binary form must reproduce the above copyright notice, this
+//    list of conditions and the following disclaimer in the documentation and/or other
+//    materials provided with the distribution.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+// ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+// WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+// IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT,
+// INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+// NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+
+use crate::error::Result;
+use crate::iter::Iter;
+
+/// `Cursor` wraps [`Iter`] to provide the B-tree like cursor operations.
+///
+/// # Examples
+///
+/// ```
+/// use mouse_leveldb::{Cursor, Database, WriteBatch};
+/// use std::ffi::CString;
+/// use tempfile;
+///
+/// let tmp = tempfile::tempdir().unwrap();
+/// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+///
+/// let mut db = Database::new();
+/// db.open(&path).unwrap();
+///
+/// let mut batch = WriteBatch::new();
+/// batch.put(&[10], &[1]);
+/// batch.put(&[20], &[2]);
+/// batch.put(&[30], &[3]);
+/// mouse_leveldb::write(&db, &mut batch).unwrap();
+///
+/// let mut cursor = Cursor::new(mouse_leveldb::iter(&db));
+///
+/// let one: &[u8] = &[1];
+/// let two: &[u8] = &[2];
+/// let three: &[u8] = &[3];
+///
+/// assert_eq!(Some((&[20][..], two)), cursor.seek_ge(&[20]));
+/// assert_eq!(Some((&[20][..], two)), cursor.seek_ge(&[15]));
+/// assert_eq!(Some((&[10][..], one)), cursor.seek_ge(&[]));
+/// assert_eq!(None, cursor.seek_ge(&[31]));
+///
+/// assert_eq!(Some((&[20][..], two)), cursor.seek_le(&[20]));
+/// assert_eq!(Some((&[20][..], two)), cursor.seek_le(&[25]));
+/// assert_eq!(Some((&[30][..], three)), cursor.seek_le(&[255]));
+/// assert_eq!(None, cursor.seek_le(&[9]));
+///
+/// cursor.status().unwrap();
+/// ```
+pub struct Cursor<'a> {
+    it: Iter<'a>,
+}
+
+impl<'a> Cursor<'a> {
+    /// Creates a new instance wrapping `it` .
+    #[inline]
+    pub fn new(it: Iter<'a>) -> Self {
+        Self { it }
+    }
+
+    /// Moves `self` to the first (key, value) pair whose key is greater than or equals to `key` ,
+    /// and returns it. Returns `None` if there is no such pair.
+    ///
+    /// The returned slices are valid until `self` moves.
+    pub fn seek_ge(&mut self, key: &[u8]) -> Option<(&[u8], &[u8])> {
+        self.it.seek(key);
+        self.entry()
+    }
+
+    /// Moves `self` to the last (key, value) pair whose key is less than or equals to `key` , and
+    /// returns it. Returns `None` if there is no such pair.
+    ///
+    /// The returned slices are valid until `self` moves.
+    pub fn seek_le(&mut self, key: &[u8]) -> Option<(&[u8], &[u8])> {
+        self.it.seek(key);
+        if !self.it.is_valid() {
+            // All the keys are less than 'key' .
+            self.it.seek_to_last();
+        } else if self.it.key() != Some(key) {
+            // 'self' points the first key greater than 'key' .
+            self.it.seek_prev();
+        }
+        self.entry()
+    }
+
+    /// Same to [`Iter::status`] .
+    #[inline]
+    pub fn status(&self) -> Result<()> {
+        self.it.status()
+    }
+
+    /// Consumes `self` and returns the wrapped [`Iter`] .
+    #[inline]
+    pub fn into_inner(self) -> Iter<'a> {
+        self.it
+    }
+
+    /// Returns the (key, value) pair `self` points if any.
+    pub fn entry(&self) -> Option<(&[u8], &[u8])> {
+        match (self.it.key(), self.it.value()) {
+            (Some(key), Some(value)) => Some((key, value)),
+            _ => None,
+        }
+    }
+}
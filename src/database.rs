@@ -51,19 +51,105 @@
 // ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
 // POSSIBILITY OF SUCH DAMAGE.
 
-use crate::error::{self, Error};
+use crate::disk_usage::{self, DiskUsage};
+use crate::error::{self, Result};
+use crate::integrity::IntegrityReport;
+use crate::iter::{self, Iter};
+use crate::octets::{self, Octets};
+use crate::options::Options;
+use crate::read_only::{self, ReadOnly};
+use crate::read_options::ReadOptions;
+use crate::scoped_db::{self, ScopedDb};
+use crate::write_batch::WriteBatch;
+use crate::write_options::WriteOptions;
 use crate::OPTIONS;
 use core::ptr::{null_mut, NonNull};
-use leveldb_sys::{leveldb_close, leveldb_open, leveldb_t};
-use std::ffi::CStr;
+use leveldb_sys::{
+    leveldb_approximate_sizes, leveldb_close, leveldb_open, leveldb_repair_db, leveldb_t,
+};
+use std::collections::{BTreeMap, HashMap};
+use std::convert::TryFrom;
+use std::ffi::{CStr, CString};
+use std::fmt;
+use std::io::{self, Read, Write};
 use std::os::raw::c_char;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// `RetryPolicy` tells [`Database::open_with_retry`] how many times and how often to try.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// The max number of trials including the first one.
+    pub max_attempts: usize,
+    /// The interval to wait for before each retry.
+    pub backoff: Duration,
+}
+
+/// `Opened` tells how [`Database::open_or_repair`] opened the database.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Opened {
+    /// The database was opened without repair.
+    Clean,
+    /// The database was corrupted, and opened after repaired.
+    Repaired,
+}
 
 /// `Database` is a wrapper of `*mut leveldb_t` to make sure to close on the drop.
-pub struct Database(Option<*mut leveldb_t>);
+pub struct Database {
+    ptr: Option<*mut leveldb_t>,
+    // The path and the options passed to 'open_with()' to reopen.
+    // The options must outlive 'ptr'.
+    path: Option<CString>,
+    options: Option<Options>,
+    read_only: bool,
+}
 
 unsafe impl Send for Database {}
 unsafe impl Sync for Database {}
 
+impl fmt::Debug for Database {
+    /// Shows whether `self` is opened or not, and the path to the database if opened.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::Database;
+    /// use std::ffi::CString;
+    /// use tempfile;
+    ///
+    /// let tmp = tempfile::tempdir().unwrap();
+    /// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+    ///
+    /// let mut db = Database::new();
+    /// assert_eq!("Database { state: Closed }", format!("{:?}", db));
+    ///
+    /// db.open(&path).unwrap();
+    /// assert_eq!(
+    ///     format!("Database {{ state: Open, path: {:?} }}", path.to_str().unwrap()),
+    ///     format!("{:?}", db)
+    /// );
+    ///
+    /// db.close();
+    /// assert_eq!("Database { state: Closed }", format!("{:?}", db));
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        #[derive(Debug)]
+        enum State {
+            Open,
+            Closed,
+        }
+
+        let mut f = f.debug_struct("Database");
+        match (self.ptr, self.path.as_ref()) {
+            (Some(_), Some(path)) => f
+                .field("state", &State::Open)
+                .field("path", &path.to_string_lossy()),
+            _ => f.field("state", &State::Closed),
+        };
+        f.finish()
+    }
+}
+
 impl Drop for Database {
     fn drop(&mut self) {
         self.close();
@@ -81,21 +167,107 @@ impl Database {
     /// let _db = Database::new();
     /// ```
     pub const fn new() -> Self {
-        Self(None)
+        Self {
+            ptr: None,
+            path: None,
+            options: None,
+            read_only: false,
+        }
     }
 
     /// Creates a database if not exists and opens.
     ///
     /// `path` is the path to the directory where database files are stored.
     ///
-    /// # Panics
+    /// It is an error if `self` has been already opened; this method returns
+    /// [`ErrorKind::AlreadyOpen`] instead of panicking.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::{Database, ErrorKind};
+    /// use std::ffi::CString;
+    /// use tempfile;
+    ///
+    /// let tmp = tempfile::tempdir().unwrap();
+    /// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+    ///
+    /// let mut db = Database::new();
+    /// db.open(&path).unwrap();
+    ///
+    /// // Opening twice is an error.
+    /// let e = db.open(&path).unwrap_err();
+    /// assert_eq!(ErrorKind::AlreadyOpen, e.kind());
+    /// assert_eq!(true, e.to_string().contains(path.to_str().unwrap()));
+    ///
+    /// // It is possible to open again after closed.
+    /// db.close();
+    /// db.open(&path).unwrap();
+    /// ```
+    ///
+    /// This method takes `&mut self` , so no other thread can open `self` concurrently. `self` is
+    /// changed only if the database is opened successfully.
+    ///
+    /// [`ErrorKind::AlreadyOpen`]: crate::ErrorKind::AlreadyOpen
+    pub fn open(&mut self, path: &CStr) -> Result<()> {
+        self.ensure_not_opened_()?;
+
+        let ptr = open_(path, &OPTIONS)?;
+        self.ptr = Some(ptr);
+        self.path = Some(path.to_owned());
+        self.options = None;
+        self.read_only = false;
+        Ok(())
+    }
+
+    /// Opens the database with `options` instead of the default [`Options`] .
+    ///
+    /// `path` is the path to the directory where database files are stored.
+    /// `self` owns `options` while opened.
     ///
-    /// Causes a panic if `self` has been already opened.
+    /// It is an error if `self` has been already opened.
     ///
     /// # Examples
     ///
     /// ```
-    /// use mouse_leveldb::Database;
+    /// use mouse_leveldb::{Database, Options};
+    /// use std::ffi::CString;
+    /// use tempfile;
+    ///
+    /// let tmp = tempfile::tempdir().unwrap();
+    /// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+    ///
+    /// let mut db = Database::new();
+    /// db.open_with(&path, Options::new()).unwrap();
+    /// ```
+    pub fn open_with(&mut self, path: &CStr, options: Options) -> Result<()> {
+        self.ensure_not_opened_()?;
+
+        let ptr = open_(path, &options)?;
+        self.ptr = Some(ptr);
+        self.path = Some(path.to_owned());
+        self.options = Some(options);
+        self.read_only = false;
+        Ok(())
+    }
+
+    /// Opens the existing database to read only.
+    ///
+    /// It is an error if the database does not exist. After opened, [`write`] and the other
+    /// functions to modify the database return an error without modifying it.
+    ///
+    /// # Warnings
+    ///
+    /// LevelDB has no read-only mode; the database is rejected to be modified only by this crate.
+    /// Opening takes the lock of the database as [`open`] does, so it fails while another process
+    /// opens the database. LevelDB may also write the log files on opening.
+    ///
+    /// It is an error if `self` has been already opened.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::{Database, WriteBatch};
     /// use std::ffi::CString;
     /// use tempfile;
     ///
@@ -103,42 +275,1296 @@ impl Database {
     /// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
     ///
     /// let mut db = Database::new();
+    /// assert!(db.open_read_only(&path).is_err());
+    ///
     /// db.open(&path).unwrap();
+    /// let mut batch = WriteBatch::new();
+    /// batch.put(&[1], &[10]);
+    /// mouse_leveldb::write(&db, &mut batch).unwrap();
+    /// db.close();
+    ///
+    /// db.open_read_only(&path).unwrap();
+    /// assert_eq!(true, db.is_read_only());
+    /// assert_eq!(&[10], mouse_leveldb::get(&db, &[1]).unwrap().as_ref());
+    ///
+    /// let mut batch = WriteBatch::new();
+    /// batch.put(&[2], &[20]);
+    /// assert_eq!(true, mouse_leveldb::write(&db, &mut batch).unwrap_err().is_read_only());
+    /// assert_eq!(0, mouse_leveldb::get(&db, &[2]).unwrap().len());
     /// ```
-    pub fn open(&mut self, path: &CStr) -> Result<(), Error> {
-        assert_eq!(None, self.0);
+    ///
+    /// [`write`]: crate::write
+    /// [`open`]: Self::open
+    pub fn open_read_only(&mut self, path: &CStr) -> Result<()> {
+        // 'error_if_exists' is false by default.
+        let mut options = Options::new();
+        options.set_create_if_missing(false);
 
-        unsafe {
-            let mut error: *mut c_char = null_mut();
-            let errptr: *mut *mut c_char = &mut error;
-
-            let ptr = leveldb_open(OPTIONS.as_ptr(), path.as_ptr(), errptr);
-            match NonNull::new(error) {
-                Some(e) => {
-                    assert_eq!(true, ptr.is_null());
-                    Err(error::new(e))
+        self.open_with(path, options)?;
+        self.read_only = true;
+        Ok(())
+    }
+
+    /// Returns `true` if `self` is opened by [`open_read_only`] .
+    ///
+    /// [`open_read_only`]: Self::open_read_only
+    #[inline]
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    /// Opens the database with `options` as [`open_with`] does, and repairs and retries to open
+    /// it once if LevelDB detects the corruption of the database.
+    ///
+    /// Returns [`Opened::Repaired`] if repaired, or [`Opened::Clean`] if opened without repair.
+    /// Errors other than the corruption (for example, lock contention or I/O error) are returned
+    /// without repair.
+    ///
+    /// # Warnings
+    ///
+    /// Repair may lose some data that was corrupted.
+    ///
+    /// It is an error if `self` has been already opened.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::{Database, Opened, Options, WriteBatch};
+    /// use std::ffi::CString;
+    /// use std::fs;
+    /// use tempfile;
+    ///
+    /// let tmp = tempfile::tempdir().unwrap();
+    /// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+    ///
+    /// let mut db = Database::new();
+    /// assert_eq!(Opened::Clean, db.open_or_repair(&path, Options::new()).unwrap());
+    ///
+    /// let key: &[u8] = &[1, 2, 3];
+    /// let value: &[u8] = &[4, 5, 6];
+    ///
+    /// let mut batch = WriteBatch::new();
+    /// batch.put(key, value);
+    /// mouse_leveldb::write(&db, &mut batch).unwrap();
+    /// db.close();
+    ///
+    /// // Breaks the checksum of the log file.
+    /// for entry in fs::read_dir(tmp.path()).unwrap() {
+    ///     let file = entry.unwrap().path();
+    ///     if file.extension().map_or(false, |ext| ext == "log") {
+    ///         let mut bytes = fs::read(&file).unwrap();
+    ///         bytes[0] = !bytes[0];
+    ///         fs::write(&file, bytes).unwrap();
+    ///     }
+    /// }
+    ///
+    /// assert!(db.open(&path).is_err());
+    /// assert_eq!(Opened::Repaired, db.open_or_repair(&path, Options::new()).unwrap());
+    ///
+    /// // The repaired database is available as usual.
+    /// let mut batch = WriteBatch::new();
+    /// batch.put(key, value);
+    /// mouse_leveldb::write(&db, &mut batch).unwrap();
+    /// assert_eq!(value, mouse_leveldb::get(&db, key).unwrap().as_ref());
+    ///
+    /// db.close();
+    /// assert_eq!(Opened::Clean, db.open_or_repair(&path, Options::new()).unwrap());
+    /// ```
+    ///
+    /// [`open_with`]: Self::open_with
+    pub fn open_or_repair(&mut self, path: &CStr, options: Options) -> Result<Opened> {
+        self.ensure_not_opened_()?;
+
+        let (ptr, opened) = match open_(path, &options) {
+            Ok(ptr) => (ptr, Opened::Clean),
+            Err(e) => {
+                if !e.is_corruption() {
+                    return Err(e);
                 }
-                None => {
-                    assert_eq!(false, ptr.is_null());
-                    self.0 = Some(ptr);
-                    Ok(())
+                repair_(path, &options)?;
+                (open_(path, &options)?, Opened::Repaired)
+            }
+        };
+
+        self.ptr = Some(ptr);
+        self.path = Some(path.to_owned());
+        self.options = Some(options);
+        self.read_only = false;
+        Ok(opened)
+    }
+
+    /// Tries to open the database as [`open`] does, and retries while another handle or another
+    /// process holds the lock of the database.
+    ///
+    /// This method sleeps `policy.backoff` before each retry, and gives up after
+    /// `policy.max_attempts` trials. The error returned on giving up tells the number of the trials
+    /// by [`Error::attempts`] .
+    ///
+    /// Errors other than the lock contention (for example, corruption) are returned immediately
+    /// without retry.
+    ///
+    /// It is an error if `self` has been already opened.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::{Database, RetryPolicy};
+    /// use std::ffi::CString;
+    /// use std::time::Duration;
+    /// use tempfile;
+    ///
+    /// let tmp = tempfile::tempdir().unwrap();
+    /// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+    ///
+    /// let policy = RetryPolicy {
+    ///     max_attempts: 3,
+    ///     backoff: Duration::from_millis(10),
+    /// };
+    ///
+    /// let mut db1 = Database::new();
+    /// db1.open(&path).unwrap();
+    ///
+    /// // 'db1' holds the lock.
+    /// let mut db2 = Database::new();
+    /// let err = db2.open_with_retry(&path, policy).unwrap_err();
+    /// assert_eq!(3, err.attempts());
+    ///
+    /// // Succeeds after 'db1' releases the lock.
+    /// db1.close();
+    /// db2.open_with_retry(&path, policy).unwrap();
+    ///
+    /// // Gives up immediately if the error is not caused by the lock.
+    /// let path = tmp.path().join("no_such_dir").join("db");
+    /// let path = CString::new(path.to_str().unwrap()).unwrap();
+    /// let mut db3 = Database::new();
+    /// let err = db3.open_with_retry(&path, policy).unwrap_err();
+    /// assert_eq!(1, err.attempts());
+    /// ```
+    ///
+    /// [`open`]: Self::open
+    pub fn open_with_retry(&mut self, path: &CStr, policy: RetryPolicy) -> Result<()> {
+        let mut attempts = 0;
+        loop {
+            attempts += 1;
+
+            match self.open(path) {
+                Ok(()) => return Ok(()),
+                Err(e) => {
+                    if !e.is_lock_held() {
+                        return Err(e);
+                    }
+                    if policy.max_attempts <= attempts {
+                        return Err(error::retry(attempts, e));
+                    }
                 }
             }
+
+            std::thread::sleep(policy.backoff);
         }
     }
 
-    /// Closes the DB and makes `self` unopend state if opened; otherwise does nothing.
-    pub fn close(&mut self) {
-        if let Some(ptr) = self.0 {
-            unsafe { leveldb_close(ptr) };
-            self.0 = None;
+    /// Estimates the size of the whole database in bytes.
+    ///
+    /// The result is the approximate size of the files on the file system; it does not include
+    /// the data in memory which has not been written into the table files yet.
+    ///
+    /// It is an error if `self` is not opened.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::{Database, ErrorKind, WriteBatch};
+    /// use std::ffi::CString;
+    /// use tempfile;
+    ///
+    /// let tmp = tempfile::tempdir().unwrap();
+    /// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+    ///
+    /// let mut db = Database::new();
+    /// assert_eq!(ErrorKind::NotOpened, db.estimate_total_size().unwrap_err().kind());
+    ///
+    /// db.open(&path).unwrap();
+    /// assert_eq!(0, db.estimate_total_size().unwrap());
+    ///
+    /// let mut batch = WriteBatch::new();
+    /// for i in 0..1000_u32 {
+    ///     batch.put(&i.to_be_bytes(), &[0; 100]);
+    /// }
+    /// mouse_leveldb::write(&db, &mut batch).unwrap();
+    ///
+    /// // Reopening writes the data in memory into the table file.
+    /// db.close();
+    /// db.open(&path).unwrap();
+    ///
+    /// assert!(0 < db.estimate_total_size().unwrap());
+    /// ```
+    pub fn estimate_total_size(&self) -> Result<u64> {
+        let ptr = self.ptr.ok_or_else(error::not_opened)?;
+
+        let mut it = crate::iter(self);
+        it.seek_to_last();
+
+        // The range is [start, limit); 'limit' is the next of the last key.
+        let limit = match it.key() {
+            None => {
+                it.status()?;
+                return Ok(0);
+            }
+            Some(key) => {
+                let mut limit = Vec::with_capacity(key.len() + 1);
+                limit.extend_from_slice(key);
+                limit.push(0);
+                limit
+            }
+        };
+        let start: &[u8] = &[];
+
+        let mut size: u64 = 0;
+        unsafe {
+            leveldb_approximate_sizes(
+                ptr,
+                1,
+                &(start.as_ptr() as *const c_char),
+                &start.len(),
+                &(limit.as_ptr() as *const c_char),
+                &limit.len(),
+                &mut size,
+            );
         }
+        Ok(size)
     }
-}
 
-/// Returns a pointer to the wrapped address.
-///
-/// Note that `leveldb_t` is `Sync` .
-pub fn as_ptr(db: &Database) -> Option<*mut leveldb_t> {
-    db.0
+    /// Returns the path to the database directory if `self` has been opened; otherwise returns
+    /// `None` .
+    ///
+    /// `self` keeps the path after closed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::Database;
+    /// use std::ffi::CString;
+    /// use tempfile;
+    ///
+    /// let tmp = tempfile::tempdir().unwrap();
+    /// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+    ///
+    /// let mut db = Database::new();
+    /// assert_eq!(None, db.path());
+    ///
+    /// db.open(&path).unwrap();
+    /// assert_eq!(Some(path.as_ref()), db.path());
+    /// ```
+    #[inline]
+    pub fn path(&self) -> Option<&CStr> {
+        self.path.as_deref()
+    }
+
+    /// Returns the path to the file where LevelDB writes the internal log, or `None` if `self`
+    /// has never been opened.
+    ///
+    /// LevelDB writes the log into file `LOG` in the database directory, and renames the old one
+    /// to `LOG.old` on open. The C API of LevelDB provides no way to move or to silence it; see
+    /// [`Options`] .
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::Database;
+    /// use std::ffi::CString;
+    /// use tempfile;
+    ///
+    /// let tmp = tempfile::tempdir().unwrap();
+    /// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+    ///
+    /// let mut db = Database::new();
+    /// assert_eq!(None, db.info_log_path());
+    ///
+    /// db.open(&path).unwrap();
+    /// let log = db.info_log_path().unwrap();
+    /// assert_eq!(tmp.path().join("LOG"), log);
+    /// assert_eq!(true, log.is_file());
+    /// ```
+    pub fn info_log_path(&self) -> Option<PathBuf> {
+        self.path
+            .as_deref()
+            .map(|path| to_path_buf(path).join("LOG"))
+    }
+
+    /// Reports the files in the database directory per file type.
+    ///
+    /// The files removed while walking the directory (for example, by the background compaction)
+    /// are skipped.
+    ///
+    /// It is an error if `self` has never been opened.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::{Database, WriteBatch};
+    /// use std::ffi::CString;
+    /// use tempfile;
+    ///
+    /// let tmp = tempfile::tempdir().unwrap();
+    /// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+    ///
+    /// let mut db = Database::new();
+    /// assert!(db.disk_usage().is_err());
+    ///
+    /// db.open(&path).unwrap();
+    ///
+    /// let mut batch = WriteBatch::new();
+    /// for i in 0..1000_u32 {
+    ///     batch.put(&i.to_be_bytes(), &[0; 100]);
+    /// }
+    /// mouse_leveldb::write(&db, &mut batch).unwrap();
+    ///
+    /// // Reopening writes the data in memory into the table file.
+    /// db.reopen().unwrap();
+    ///
+    /// let usage = db.disk_usage().unwrap();
+    /// assert!(0 < usage.total.bytes);
+    /// assert!(0 < usage.tables.files);
+    /// assert_eq!(1, usage.lock.files);
+    /// assert_eq!(1, usage.current.files);
+    ///
+    /// let mut sum = usage.tables;
+    /// sum += usage.logs;
+    /// sum += usage.manifests;
+    /// sum += usage.lock;
+    /// sum += usage.current;
+    /// sum += usage.other;
+    /// assert_eq!(usage.total, sum);
+    /// ```
+    pub fn disk_usage(&self) -> Result<DiskUsage> {
+        let path = self.path.as_ref().ok_or_else(error::not_opened)?;
+        disk_usage::walk(&to_path_buf(path)).map_err(error::io)
+    }
+
+    /// Counts the keys stored in `self` .
+    ///
+    /// LevelDB does not track the number of the keys, so this method iterates all the keys; it
+    /// takes O(n) time. For a large database, consider maintaining the number in a counter key
+    /// updated in the same [`WriteBatch`] as the data.
+    ///
+    /// It is an error if `self` is not opened.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::{Database, WriteBatch};
+    /// use std::ffi::CString;
+    /// use tempfile;
+    ///
+    /// let tmp = tempfile::tempdir().unwrap();
+    /// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+    ///
+    /// let mut db = Database::new();
+    /// assert!(db.count().is_err());
+    ///
+    /// db.open(&path).unwrap();
+    /// assert_eq!(0, db.count().unwrap());
+    ///
+    /// let mut batch = WriteBatch::new();
+    /// for i in 0..10_u8 {
+    ///     batch.put(&[i], &[i]);
+    /// }
+    /// mouse_leveldb::write(&db, &mut batch).unwrap();
+    /// assert_eq!(10, db.count().unwrap());
+    ///
+    /// batch.delete(&[0]);
+    /// batch.delete(&[1]);
+    /// batch.delete(&[100]);
+    /// mouse_leveldb::write(&db, &mut batch).unwrap();
+    /// assert_eq!(8, db.count().unwrap());
+    /// ```
+    ///
+    /// [`WriteBatch`]: crate::WriteBatch
+    #[inline]
+    pub fn count(&self) -> Result<u64> {
+        crate::count(self)
+    }
+
+    /// Returns the (key, value) pair with the smallest key, or `None` if `self` is empty.
+    ///
+    /// It is an error if `self` is not opened.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::{Database, WriteBatch};
+    /// use std::ffi::CString;
+    /// use tempfile;
+    ///
+    /// let tmp = tempfile::tempdir().unwrap();
+    /// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+    ///
+    /// let mut db = Database::new();
+    /// db.open(&path).unwrap();
+    /// assert_eq!(true, db.first_key_value().unwrap().is_none());
+    ///
+    /// let mut batch = WriteBatch::new();
+    /// batch.put(&[2], &[20]);
+    /// batch.put(&[1], &[10]);
+    /// batch.put(&[3], &[30]);
+    /// mouse_leveldb::write(&db, &mut batch).unwrap();
+    ///
+    /// let (key, value) = db.first_key_value().unwrap().unwrap();
+    /// assert_eq!(&[1], key.as_ref());
+    /// assert_eq!(&[10], value.as_ref());
+    /// ```
+    pub fn first_key_value(&self) -> Result<Option<(Octets, Octets)>> {
+        self.edge_key_value_(|it| it.seek_to_first())
+    }
+
+    /// Returns the (key, value) pair with the largest key, or `None` if `self` is empty.
+    ///
+    /// It is an error if `self` is not opened.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::{Database, WriteBatch};
+    /// use std::ffi::CString;
+    /// use tempfile;
+    ///
+    /// let tmp = tempfile::tempdir().unwrap();
+    /// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+    ///
+    /// let mut db = Database::new();
+    /// db.open(&path).unwrap();
+    /// assert_eq!(true, db.last_key_value().unwrap().is_none());
+    ///
+    /// let mut batch = WriteBatch::new();
+    /// batch.put(&[2], &[20]);
+    /// batch.put(&[1], &[10]);
+    /// batch.put(&[3], &[30]);
+    /// mouse_leveldb::write(&db, &mut batch).unwrap();
+    ///
+    /// let (key, value) = db.last_key_value().unwrap().unwrap();
+    /// assert_eq!(&[3], key.as_ref());
+    /// assert_eq!(&[30], value.as_ref());
+    /// ```
+    pub fn last_key_value(&self) -> Result<Option<(Octets, Octets)>> {
+        self.edge_key_value_(|it| it.seek_to_last())
+    }
+
+    /// Returns at most `limit` (key, value) pairs whose key is greater than or equal to `start`
+    /// in the order of the key.
+    ///
+    /// Returns an empty vector if `limit` is 0 or if no key is greater than or equal to `start` .
+    ///
+    /// To read the next page, pass the last key appended `0x00` as `start` ; it is the smallest
+    /// key greater than the last key.
+    ///
+    /// It is an error if `self` is not opened.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::{Database, WriteBatch};
+    /// use std::ffi::CString;
+    /// use tempfile;
+    ///
+    /// let tmp = tempfile::tempdir().unwrap();
+    /// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+    ///
+    /// let mut db = Database::new();
+    /// db.open(&path).unwrap();
+    ///
+    /// let mut batch = WriteBatch::new();
+    /// for i in 1..=8 {
+    ///     batch.put(&[i], &[i * 10]);
+    /// }
+    /// mouse_leveldb::write(&db, &mut batch).unwrap();
+    ///
+    /// // Paginates in pages of 3.
+    /// let mut pages = Vec::new();
+    /// let mut start = Vec::new();
+    /// loop {
+    ///     let page = db.scan_with_limit(&start, 3).unwrap();
+    ///     let keys: Vec<u8> = page.iter().map(|(k, _)| k[0]).collect();
+    ///     match page.last() {
+    ///         None => break,
+    ///         Some((last, _)) => {
+    ///             start = last.to_vec();
+    ///             start.push(0x00);
+    ///         }
+    ///     }
+    ///     pages.push(keys);
+    /// }
+    /// assert_eq!(vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8]], pages);
+    ///
+    /// let page = db.scan_with_limit(&[5], 2).unwrap();
+    /// assert_eq!(2, page.len());
+    /// assert_eq!(&[5], page[0].0.as_ref());
+    /// assert_eq!(&[60], page[1].1.as_ref());
+    ///
+    /// assert_eq!(true, db.scan_with_limit(&[1], 0).unwrap().is_empty());
+    /// assert_eq!(true, db.scan_with_limit(&[9], 3).unwrap().is_empty());
+    /// ```
+    pub fn scan_with_limit(&self, start: &[u8], limit: usize) -> Result<Vec<(Octets, Octets)>> {
+        if self.ptr.is_none() {
+            return Err(error::not_opened());
+        }
+
+        let mut ret = Vec::new();
+        if limit == 0 {
+            return Ok(ret);
+        }
+
+        let mut it = crate::iter(self);
+        it.seek(start);
+        while let (Some(key), Some(value)) = (it.key(), it.value()) {
+            ret.push((octets::from_slice(key)?, octets::from_slice(value)?));
+            if ret.len() == limit {
+                break;
+            }
+            it.seek_next();
+        }
+
+        it.status().map(|_| ret)
+    }
+
+    fn edge_key_value_<F>(&self, seek: F) -> Result<Option<(Octets, Octets)>>
+    where
+        F: FnOnce(&mut Iter),
+    {
+        if self.ptr.is_none() {
+            return Err(error::not_opened());
+        }
+
+        let mut it = crate::iter(self);
+        seek(&mut it);
+        match (it.key(), it.value()) {
+            (Some(key), Some(value)) => {
+                Ok(Some((octets::from_slice(key)?, octets::from_slice(value)?)))
+            }
+            _ => it.status().map(|_| None),
+        }
+    }
+
+    /// Reads all the (key, value) pairs verifying the checksums and returns the report.
+    ///
+    /// The errors found while reading are collected into the report instead of aborting the scan;
+    /// see [`IntegrityReport::errors`] to know near which key each error occurred.
+    ///
+    /// It is an error if `self` is not opened.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::{Database, WriteBatch};
+    /// use std::ffi::CString;
+    /// use std::fs;
+    /// use tempfile;
+    ///
+    /// let tmp = tempfile::tempdir().unwrap();
+    /// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+    ///
+    /// let mut db = Database::new();
+    /// assert!(db.verify_integrity().is_err());
+    ///
+    /// db.open(&path).unwrap();
+    ///
+    /// let mut batch = WriteBatch::new();
+    /// for i in 0..1000_u32 {
+    ///     batch.put(&i.to_be_bytes(), &[0; 10]);
+    /// }
+    /// mouse_leveldb::write(&db, &mut batch).unwrap();
+    ///
+    /// // Reopening writes the data in memory into the table file.
+    /// db.reopen().unwrap();
+    ///
+    /// let report = db.verify_integrity().unwrap();
+    /// assert_eq!(true, report.is_ok());
+    /// assert_eq!(1000, report.keys);
+    /// assert_eq!(1000 * (4 + 10), report.bytes);
+    ///
+    /// // Break the table files.
+    /// db.close();
+    /// for entry in fs::read_dir(tmp.path()).unwrap() {
+    ///     let file = entry.unwrap().path();
+    ///     if file.extension().map(|ext| ext == "ldb").unwrap_or(false) {
+    ///         let len = fs::metadata(&file).unwrap().len() as usize;
+    ///         fs::write(&file, vec![0; len]).unwrap();
+    ///     }
+    /// }
+    ///
+    /// db.open(&path).unwrap();
+    /// let report = db.verify_integrity().unwrap();
+    /// assert_eq!(false, report.is_ok());
+    /// assert_eq!(0, report.keys);
+    /// assert_eq!(1, report.errors.len());
+    /// assert_eq!(None, report.errors[0].after_key);
+    /// ```
+    ///
+    /// [`IntegrityReport::errors`]: crate::IntegrityReport::errors
+    pub fn verify_integrity(&self) -> Result<IntegrityReport> {
+        if self.ptr.is_none() {
+            return Err(error::not_opened());
+        }
+
+        let mut options = ReadOptions::new();
+        options.set_verify_checksums(true);
+        let mut it = iter::new(self, &options);
+        Ok(IntegrityReport::scan(&mut it))
+    }
+
+    /// Reads all the (key, value) pairs verifying the checksums, and returns the first error
+    /// found.
+    ///
+    /// This is a lighter version of [`verify_integrity`] , which continues the scan after an
+    /// error and reports the details.
+    ///
+    /// It is an error if `self` is not opened.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::{Database, ErrorKind, WriteBatch};
+    /// use std::ffi::CString;
+    /// use std::fs;
+    /// use tempfile;
+    ///
+    /// let tmp = tempfile::tempdir().unwrap();
+    /// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+    ///
+    /// let mut db = Database::new();
+    /// assert_eq!(ErrorKind::NotOpened, db.verify().unwrap_err().kind());
+    ///
+    /// db.open(&path).unwrap();
+    ///
+    /// let mut batch = WriteBatch::new();
+    /// for i in 0..1000_u32 {
+    ///     batch.put(&i.to_be_bytes(), &[0; 10]);
+    /// }
+    /// mouse_leveldb::write(&db, &mut batch).unwrap();
+    ///
+    /// // Reopening writes the data in memory into the table file.
+    /// db.reopen().unwrap();
+    /// db.verify().unwrap();
+    ///
+    /// // Break the table files.
+    /// db.close();
+    /// for entry in fs::read_dir(tmp.path()).unwrap() {
+    ///     let file = entry.unwrap().path();
+    ///     if file.extension().map(|ext| ext == "ldb").unwrap_or(false) {
+    ///         let len = fs::metadata(&file).unwrap().len() as usize;
+    ///         fs::write(&file, vec![0; len]).unwrap();
+    ///     }
+    /// }
+    ///
+    /// db.open(&path).unwrap();
+    /// assert_eq!(ErrorKind::Corruption, db.verify().unwrap_err().kind());
+    /// ```
+    ///
+    /// [`verify_integrity`]: Self::verify_integrity
+    pub fn verify(&self) -> Result<()> {
+        if self.ptr.is_none() {
+            return Err(error::not_opened());
+        }
+
+        let mut options = ReadOptions::new();
+        options.set_verify_checksums(true);
+        options.set_fill_cache(false);
+
+        let mut it = iter::new(self, &options);
+        it.seek_to_first();
+        while it.key().is_some() {
+            it.seek_next();
+        }
+        it.status()
+    }
+
+    /// Returns a view of `self` which provides only the methods not to modify the database.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::Database;
+    /// use std::ffi::CString;
+    /// use tempfile;
+    ///
+    /// let tmp = tempfile::tempdir().unwrap();
+    /// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+    ///
+    /// let mut db = Database::new();
+    /// db.open(&path).unwrap();
+    ///
+    /// let view = db.read_only_view();
+    /// assert_eq!(0, view.count().unwrap());
+    ///
+    /// // 'ReadOnly' can be shared among threads as well as 'Database'.
+    /// fn assert_send_sync<T: Send + Sync>(_: &T) {}
+    /// assert_send_sync(&view);
+    /// ```
+    pub fn read_only_view(&self) -> ReadOnly<'_> {
+        read_only::new(self)
+    }
+
+    /// Calls `f` with a view of `self` which writes with `options` , and returns what `f` returns.
+    ///
+    /// `options` is dropped after `f` returns.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::{Database, WriteBatch, WriteOptions};
+    /// use std::ffi::CString;
+    /// use tempfile;
+    ///
+    /// let tmp = tempfile::tempdir().unwrap();
+    /// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+    ///
+    /// let mut db = Database::new();
+    /// db.open(&path).unwrap();
+    ///
+    /// let mut options = WriteOptions::new();
+    /// options.set_sync(true);
+    ///
+    /// let written = db.with_write_options(options, |scoped| {
+    ///     scoped.put(&[1], &[10])?;
+    ///     scoped.put(&[2], &[20])?;
+    ///     scoped.delete(&[1])?;
+    ///
+    ///     let mut batch = WriteBatch::new();
+    ///     batch.put(&[3], &[30]);
+    ///     scoped.write(&mut batch)?;
+    ///     assert_eq!(0, batch.len());
+    ///
+    ///     mouse_leveldb::count(scoped.database())
+    /// });
+    /// assert_eq!(2, written.unwrap());
+    ///
+    /// assert_eq!(0, mouse_leveldb::get(&db, &[1]).unwrap().len());
+    /// assert_eq!(&[20], mouse_leveldb::get(&db, &[2]).unwrap().as_ref());
+    /// assert_eq!(&[30], mouse_leveldb::get(&db, &[3]).unwrap().as_ref());
+    ///
+    /// // It is an error to write if 'db' is not opened.
+    /// db.close();
+    /// let ret = db.with_write_options(WriteOptions::new(), |scoped| scoped.put(&[4], &[40]));
+    /// assert!(ret.is_err());
+    /// ```
+    pub fn with_write_options<R, F>(&self, options: WriteOptions, f: F) -> R
+    where
+        F: FnOnce(&ScopedDb) -> R,
+    {
+        f(&scoped_db::new(self, &options))
+    }
+
+    /// Reads all the (key, value) pairs stored in `self` into a `BTreeMap` .
+    ///
+    /// It is an error if `self` is not opened.
+    ///
+    /// # Warnings
+    ///
+    /// This method copies all the data in `self` on the memory. It may exhaust the OS memory if
+    /// `self` is large. It is intended for small databases and tests.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::{Database, WriteBatch};
+    /// use std::collections::BTreeMap;
+    /// use std::ffi::CString;
+    /// use tempfile;
+    ///
+    /// let tmp = tempfile::tempdir().unwrap();
+    /// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+    ///
+    /// let mut db = Database::new();
+    /// db.open(&path).unwrap();
+    ///
+    /// let mut expected = BTreeMap::new();
+    /// expected.insert(vec![1], vec![10]);
+    /// expected.insert(vec![2, 3], vec![]);
+    /// expected.insert(vec![4], vec![40, 41]);
+    ///
+    /// let mut batch = WriteBatch::new();
+    /// for (k, v) in expected.iter() {
+    ///     batch.put(k, v);
+    /// }
+    /// mouse_leveldb::write(&db, &mut batch).unwrap();
+    ///
+    /// assert_eq!(expected, db.to_btree_map().unwrap());
+    /// ```
+    pub fn to_btree_map(&self) -> Result<BTreeMap<Vec<u8>, Vec<u8>>> {
+        let mut ret = BTreeMap::new();
+        self.for_each_(|k, v| {
+            ret.insert(k.to_vec(), v.to_vec());
+        })?;
+        Ok(ret)
+    }
+
+    /// Reads all the (key, value) pairs stored in `self` into a `HashMap` .
+    ///
+    /// It is an error if `self` is not opened.
+    ///
+    /// # Warnings
+    ///
+    /// This method copies all the data in `self` on the memory. It may exhaust the OS memory if
+    /// `self` is large. It is intended for small databases and tests.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::{Database, WriteBatch};
+    /// use std::collections::HashMap;
+    /// use std::ffi::CString;
+    /// use tempfile;
+    ///
+    /// let tmp = tempfile::tempdir().unwrap();
+    /// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+    ///
+    /// let mut db = Database::new();
+    /// db.open(&path).unwrap();
+    ///
+    /// let mut expected = HashMap::new();
+    /// expected.insert(vec![1], vec![10]);
+    /// expected.insert(vec![2, 3], vec![]);
+    /// expected.insert(vec![4], vec![40, 41]);
+    ///
+    /// let mut batch = WriteBatch::new();
+    /// for (k, v) in expected.iter() {
+    ///     batch.put(k, v);
+    /// }
+    /// mouse_leveldb::write(&db, &mut batch).unwrap();
+    ///
+    /// assert_eq!(expected, db.to_hash_map().unwrap());
+    /// ```
+    pub fn to_hash_map(&self) -> Result<HashMap<Vec<u8>, Vec<u8>>> {
+        let mut ret = HashMap::new();
+        self.for_each_(|k, v| {
+            ret.insert(k.to_vec(), v.to_vec());
+        })?;
+        Ok(ret)
+    }
+
+    /// Stores all the (key, value) pairs in `map` into `self` at once.
+    ///
+    /// This method is the counterpart of [`to_btree_map`] to restore the data.
+    ///
+    /// It is an error if `self` is not opened.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::{Database, WriteBatch};
+    /// use std::ffi::CString;
+    /// use tempfile;
+    ///
+    /// let tmp1 = tempfile::tempdir().unwrap();
+    /// let path1 = CString::new(tmp1.path().to_str().unwrap()).unwrap();
+    /// let tmp2 = tempfile::tempdir().unwrap();
+    /// let path2 = CString::new(tmp2.path().to_str().unwrap()).unwrap();
+    ///
+    /// let mut db1 = Database::new();
+    /// db1.open(&path1).unwrap();
+    ///
+    /// let mut batch = WriteBatch::new();
+    /// batch.put(&[1], &[10]);
+    /// batch.put(&[2, 3], &[]);
+    /// mouse_leveldb::write(&db1, &mut batch).unwrap();
+    ///
+    /// let mut db2 = Database::new();
+    /// db2.open(&path2).unwrap();
+    ///
+    /// let map = db1.to_btree_map().unwrap();
+    /// db2.load_from(&map).unwrap();
+    /// assert_eq!(map, db2.to_btree_map().unwrap());
+    /// ```
+    ///
+    /// [`to_btree_map`]: Self::to_btree_map
+    pub fn load_from(&self, map: &BTreeMap<Vec<u8>, Vec<u8>>) -> Result<()> {
+        let mut batch = WriteBatch::new();
+        for (k, v) in map.iter() {
+            batch.put(k, v);
+        }
+        crate::write(self, &mut batch)
+    }
+
+    /// Writes all the (key, value) pairs stored in `self` into `out` in the order of the key.
+    ///
+    /// Unlike [`to_btree_map`] , this method streams the pairs, so the memory usage does not
+    /// depend on the size of `self` . [`import`] reads the output to restore the data.
+    ///
+    /// Each pair is written as the following, and nothing else is written.
+    ///
+    /// | bytes | content                                     |
+    /// |-------|---------------------------------------------|
+    /// | 4     | the length of the key (u32 little-endian)   |
+    /// | N     | the key                                     |
+    /// | 4     | the length of the value (u32 little-endian) |
+    /// | M     | the value                                   |
+    ///
+    /// It is an error if writing into `out` fails, or if a key or a value is longer than
+    /// `u32::MAX` .
+    ///
+    /// It is an error if `self` is not opened.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::{Database, WriteBatch};
+    /// use std::ffi::CString;
+    /// use tempfile;
+    ///
+    /// let tmp1 = tempfile::tempdir().unwrap();
+    /// let path1 = CString::new(tmp1.path().to_str().unwrap()).unwrap();
+    /// let tmp2 = tempfile::tempdir().unwrap();
+    /// let path2 = CString::new(tmp2.path().to_str().unwrap()).unwrap();
+    ///
+    /// let mut db1 = Database::new();
+    /// db1.open(&path1).unwrap();
+    ///
+    /// let mut batch = WriteBatch::new();
+    /// batch.put(&[1], &[10, 11]);
+    /// batch.put(&[2, 3], &[]);
+    /// mouse_leveldb::write(&db1, &mut batch).unwrap();
+    ///
+    /// let mut buffer = Vec::new();
+    /// db1.export(&mut buffer).unwrap();
+    /// assert_eq!(
+    ///     vec![1, 0, 0, 0, 1, 2, 0, 0, 0, 10, 11, 2, 0, 0, 0, 2, 3, 0, 0, 0, 0],
+    ///     buffer
+    /// );
+    ///
+    /// let mut db2 = Database::new();
+    /// db2.open(&path2).unwrap();
+    ///
+    /// db2.import(&mut buffer.as_slice()).unwrap();
+    /// assert_eq!(db1.to_btree_map().unwrap(), db2.to_btree_map().unwrap());
+    /// ```
+    ///
+    /// [`to_btree_map`]: Self::to_btree_map
+    /// [`import`]: Self::import
+    pub fn export<W>(&self, out: &mut W) -> Result<()>
+    where
+        W: Write,
+    {
+        if self.ptr.is_none() {
+            return Err(error::not_opened());
+        }
+
+        let mut it = crate::iter(self);
+        it.seek_to_first();
+
+        while let (Some(k), Some(v)) = (it.key(), it.value()) {
+            write_frame_(out, k).map_err(error::io)?;
+            write_frame_(out, v).map_err(error::io)?;
+            it.seek_next();
+        }
+
+        it.status()?;
+        out.flush().map_err(error::io)
+    }
+
+    /// Reads the (key, value) pairs written by [`export`] from `input` and stores them into
+    /// `self` , and returns how many pairs are stored.
+    ///
+    /// The pairs are written every some mega bytes, so the memory usage does not depend on the
+    /// size of `input` .
+    ///
+    /// It is an error if reading `input` fails, or if `input` ends in the middle of a pair; i.e.
+    /// if `input` is truncated.
+    ///
+    /// It is an error if `self` is not opened.
+    ///
+    /// # Warnings
+    ///
+    /// This method is not atomic. Some of the pairs read before the error may be left stored in
+    /// `self` .
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::{Database, ErrorKind};
+    /// use std::ffi::CString;
+    /// use tempfile;
+    ///
+    /// let tmp = tempfile::tempdir().unwrap();
+    /// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+    ///
+    /// let mut db = Database::new();
+    /// db.open(&path).unwrap();
+    ///
+    /// let input: &[u8] = &[1, 0, 0, 0, 1, 2, 0, 0, 0, 10, 11, 1, 0, 0, 0, 2, 0, 0, 0, 0];
+    /// assert_eq!(2, db.import(&mut &input[..]).unwrap());
+    /// assert_eq!(&[10, 11], mouse_leveldb::get(&db, &[1]).unwrap().as_ref());
+    /// let value = mouse_leveldb::get_with_options(&db, &[2], &Default::default()).unwrap();
+    /// assert_eq!(Some(&[] as &[u8]), value.as_deref());
+    ///
+    /// assert_eq!(0, db.import(&mut &[][..]).unwrap());
+    ///
+    /// // The input ends in the middle of the value.
+    /// let e = db.import(&mut &input[..10]).unwrap_err();
+    /// assert_eq!(ErrorKind::IoError, e.kind());
+    ///
+    /// // The input ends in the middle of the length.
+    /// let e = db.import(&mut &input[..13]).unwrap_err();
+    /// assert_eq!(ErrorKind::IoError, e.kind());
+    /// ```
+    ///
+    /// [`export`]: Self::export
+    pub fn import<R>(&self, input: &mut R) -> Result<u64>
+    where
+        R: Read,
+    {
+        if self.ptr.is_none() {
+            return Err(error::not_opened());
+        }
+
+        const FLUSH_SIZE: usize = 4 * 1024 * 1024;
+
+        let mut batch = WriteBatch::new();
+        let mut size = 0;
+        let mut n: u64 = 0;
+
+        while let Some(k) = read_frame_(input, true).map_err(error::io)? {
+            let v = read_frame_(input, false).map_err(error::io)?.unwrap();
+            batch.put(&k, &v);
+            size += k.len() + v.len();
+            n += 1;
+
+            if FLUSH_SIZE <= size {
+                crate::write(self, &mut batch)?;
+                size = 0;
+            }
+        }
+
+        crate::write(self, &mut batch)?;
+        Ok(n)
+    }
+
+    /// Calls `f` for each (key, value) pair stored in `self` in the order of the key.
+    fn for_each_<F>(&self, mut f: F) -> Result<()>
+    where
+        F: FnMut(&[u8], &[u8]),
+    {
+        if self.ptr.is_none() {
+            return Err(error::not_opened());
+        }
+
+        let mut it = crate::iter(self);
+        it.seek_to_first();
+
+        while let (Some(k), Some(v)) = (it.key(), it.value()) {
+            f(k, v);
+            it.seek_next();
+        }
+
+        it.status()
+    }
+
+    /// Closes the DB and opens it again with the path and the options used to open the last time.
+    ///
+    /// `self` is opened if this method succeeded, or is closed if failed.
+    /// It is an error if `self` has never been opened.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::{Database, WriteBatch};
+    /// use std::ffi::CString;
+    /// use tempfile;
+    ///
+    /// let tmp = tempfile::tempdir().unwrap();
+    /// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+    ///
+    /// let mut db = Database::new();
+    /// assert!(db.reopen().is_err());
+    ///
+    /// db.open(&path).unwrap();
+    ///
+    /// let key: &[u8] = &[1, 2, 3];
+    /// let value: &[u8] = &[4, 5];
+    ///
+    /// let mut batch = WriteBatch::new();
+    /// batch.put(key, value);
+    /// mouse_leveldb::write(&db, &mut batch).unwrap();
+    ///
+    /// db.reopen().unwrap();
+    /// assert_eq!(value, mouse_leveldb::get(&db, key).unwrap().as_ref());
+    ///
+    /// // The closed database can be reopened as well.
+    /// db.close();
+    /// db.reopen().unwrap();
+    /// assert_eq!(value, mouse_leveldb::get(&db, key).unwrap().as_ref());
+    /// ```
+    pub fn reopen(&mut self) -> Result<()> {
+        let path = self.path.clone().ok_or_else(error::not_opened)?;
+        self.close();
+
+        let options = self.options.as_ref().unwrap_or(&OPTIONS);
+        let ptr = open_(&path, options)?;
+        self.ptr = Some(ptr);
+        Ok(())
+    }
+
+    /// Returns an error if `self` is opened.
+    fn ensure_not_opened_(&self) -> Result<()> {
+        match self.ptr {
+            None => Ok(()),
+            Some(_) => Err(error::already_open(
+                self.path.as_deref().unwrap_or_default(),
+            )),
+        }
+    }
+
+    /// Closes the DB and makes `self` unopend state if opened; otherwise does nothing.
+    ///
+    /// This method takes `&mut self` , so the borrow checker guarantees that no other thread is
+    /// accessing `self` (for example, calling [`get`] or holding an [`Iter`] ,) while closing.
+    /// The drop does as well. The following code does not compile.
+    ///
+    /// ```compile_fail,E0502
+    /// use mouse_leveldb::Database;
+    /// use std::ffi::CString;
+    /// use std::thread;
+    /// use tempfile;
+    ///
+    /// let tmp = tempfile::tempdir().unwrap();
+    /// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+    ///
+    /// let mut db = Database::new();
+    /// db.open(&path).unwrap();
+    ///
+    /// thread::scope(|s| {
+    ///     s.spawn(|| mouse_leveldb::get(&db, &[1, 2, 3]));
+    ///     db.close();
+    /// });
+    /// ```
+    ///
+    /// ```compile_fail,E0502
+    /// use mouse_leveldb::Database;
+    /// use std::ffi::CString;
+    /// use tempfile;
+    ///
+    /// let tmp = tempfile::tempdir().unwrap();
+    /// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+    ///
+    /// let mut db = Database::new();
+    /// db.open(&path).unwrap();
+    ///
+    /// let mut it = mouse_leveldb::iter(&db);
+    /// db.close();
+    /// it.seek_to_first();
+    /// ```
+    ///
+    /// [`get`]: crate::get
+    /// [`Iter`]: crate::Iter
+    pub fn close(&mut self) {
+        if let Some(ptr) = self.ptr {
+            unsafe { leveldb_close(ptr) };
+            self.ptr = None;
+        }
+    }
+}
+
+fn open_(path: &CStr, options: &Options) -> Result<*mut leveldb_t> {
+    unsafe {
+        let mut error: *mut c_char = null_mut();
+        let errptr: *mut *mut c_char = &mut error;
+
+        let ptr = leveldb_open(options.as_ptr(), path.as_ptr(), errptr);
+
+        #[cfg(feature = "testing")]
+        let ptr = if crate::fault::is_injected(crate::Fault::OpenNullHandle) && !ptr.is_null() {
+            leveldb_close(ptr);
+            null_mut()
+        } else {
+            ptr
+        };
+
+        match (NonNull::new(error), NonNull::new(ptr)) {
+            (Some(e), ptr) => {
+                // LevelDB never returns both the handle and the error; close it just in case.
+                if let Some(ptr) = ptr {
+                    leveldb_close(ptr.as_ptr());
+                }
+                Err(error::with_operation("open", path, None, error::new(e)))
+            }
+            (None, Some(ptr)) => Ok(ptr.as_ptr()),
+            (None, None) => {
+                let e = error::internal("leveldb_open returned neither a handle nor an error");
+                Err(error::with_operation("open", path, None, e))
+            }
+        }
+    }
+}
+
+#[cfg(unix)]
+fn to_path_buf(path: &CStr) -> PathBuf {
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+
+    PathBuf::from(OsStr::from_bytes(path.to_bytes()))
+}
+
+#[cfg(not(unix))]
+fn to_path_buf(path: &CStr) -> PathBuf {
+    PathBuf::from(path.to_string_lossy().as_ref())
+}
+
+fn repair_(path: &CStr, options: &Options) -> Result<()> {
+    unsafe {
+        let mut error: *mut c_char = null_mut();
+        let errptr: *mut *mut c_char = &mut error;
+
+        leveldb_repair_db(options.as_ptr(), path.as_ptr(), errptr);
+        match NonNull::new(error) {
+            Some(e) => Err(error::with_operation("repair", path, None, error::new(e))),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Returns a pointer to the wrapped address.
+///
+/// Note that `leveldb_t` is `Sync` .
+pub fn as_ptr(db: &Database) -> Option<*mut leveldb_t> {
+    db.ptr
+}
+
+/// Writes the length of `bytes` in u32 little-endian and `bytes` into `out` .
+fn write_frame_<W>(out: &mut W, bytes: &[u8]) -> io::Result<()>
+where
+    W: Write,
+{
+    let len = u32::try_from(bytes.len()).map_err(|_| {
+        let msg = "the key or the value is too long to export";
+        io::Error::new(io::ErrorKind::InvalidInput, msg)
+    })?;
+
+    out.write_all(&len.to_le_bytes())?;
+    out.write_all(bytes)
+}
+
+/// Reads the bytes written by `write_frame_` from `input` .
+///
+/// Returns `None` if `input` reaches the end before reading anything and `eof_ok` is `true` .
+fn read_frame_<R>(input: &mut R, eof_ok: bool) -> io::Result<Option<Vec<u8>>>
+where
+    R: Read,
+{
+    let mut len = [0; 4];
+    let mut n = 0;
+    while n < len.len() {
+        match input.read(&mut len[n..]) {
+            Ok(0) if n == 0 && eof_ok => return Ok(None),
+            Ok(0) => return Err(io::ErrorKind::UnexpectedEof.into()),
+            Ok(m) => n += m,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        }
+    }
+
+    let len = u32::from_le_bytes(len) as usize;
+    let mut ret = Vec::new();
+    input.take(len as u64).read_to_end(&mut ret)?;
+    if ret.len() < len {
+        return Err(io::ErrorKind::UnexpectedEof.into());
+    }
+    Ok(Some(ret))
 }
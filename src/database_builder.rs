@@ -0,0 +1,212 @@
+// Copyright 2021 Shin Yoshida
+//
+// "LGPL-3.0-or-later OR Apache-2.0 OR BSD-2-Clause"
+//
+// This is part of mouse-leveldb
+//
+//  mouse-leveldb is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU Lesser General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  mouse-leveldb is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU Lesser General Public License for more details.
+//
+//  You should have received a copy of the GNU Lesser General Public License
+//  along with mouse-leveldb.  If not, see <http://www.gnu.org/licenses/>.
+//
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted
+// provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of
+//    conditions and the following disclaimer.
+// 2. Redistributions in binary form must reproduce the above copyright notice, this
+//    list of conditions and the following disclaimer in the documentation and/or other
+//    materials provided with the distribution.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+// ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+// WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+// IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT,
+// INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+// NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+
+use crate::cache::Cache;
+use crate::database::Database;
+use crate::error::Result;
+use crate::options::Options;
+use std::ffi::CStr;
+use std::os::raw::c_int;
+
+/// `DatabaseBuilder` configures [`Options`] and opens [`Database`] with them.
+///
+/// # Examples
+///
+/// ```
+/// use mouse_leveldb::DatabaseBuilder;
+/// use std::ffi::CString;
+/// use tempfile;
+///
+/// let tmp = tempfile::tempdir().unwrap();
+/// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+///
+/// let _db = DatabaseBuilder::new()
+///     .create_if_missing(true)
+///     .cache_size(8 << 20)
+///     .bloom_filter(10)
+///     .compression(true)
+///     .open(&path)
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct DatabaseBuilder {
+    create_if_missing: bool,
+    cache: Option<CacheSetting>,
+    bloom_filter: Option<c_int>,
+    compression: bool,
+}
+
+#[derive(Debug, Clone)]
+enum CacheSetting {
+    Size(usize),
+    Shared(Cache),
+}
+
+impl Default for DatabaseBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DatabaseBuilder {
+    /// Creates a new instance with the same configuration to [`Options::new`] .
+    pub const fn new() -> Self {
+        Self {
+            create_if_missing: true,
+            cache: None,
+            bloom_filter: None,
+            compression: true,
+        }
+    }
+
+    /// See [`Options::set_create_if_missing`] .
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::DatabaseBuilder;
+    /// use std::ffi::CString;
+    /// use tempfile;
+    ///
+    /// let tmp = tempfile::tempdir().unwrap();
+    /// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+    ///
+    /// let builder = DatabaseBuilder::new().create_if_missing(false);
+    /// assert!(builder.open(&path).is_err());
+    ///
+    /// let builder = builder.create_if_missing(true);
+    /// assert!(builder.open(&path).is_ok());
+    /// ```
+    pub fn create_if_missing(mut self, create_if_missing: bool) -> Self {
+        self.create_if_missing = create_if_missing;
+        self
+    }
+
+    /// See [`Options::set_cache_size`] .
+    ///
+    /// Each database opened by `self` creates its own cache.
+    pub fn cache_size(mut self, capacity: usize) -> Self {
+        self.cache = Some(CacheSetting::Size(capacity));
+        self
+    }
+
+    /// See [`Options::set_cache`] .
+    ///
+    /// All the databases opened by `self` share `cache` .
+    pub fn cache(mut self, cache: Cache) -> Self {
+        self.cache = Some(CacheSetting::Shared(cache));
+        self
+    }
+
+    /// See [`Options::set_bloom_filter`] .
+    pub fn bloom_filter(mut self, bits_per_key: c_int) -> Self {
+        self.bloom_filter = Some(bits_per_key);
+        self
+    }
+
+    /// See [`Options::set_compression`] .
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::{DatabaseBuilder, WriteBatch};
+    /// use std::ffi::CString;
+    /// use tempfile;
+    ///
+    /// let tmp = tempfile::tempdir().unwrap();
+    /// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+    ///
+    /// let db = DatabaseBuilder::new()
+    ///     .compression(false)
+    ///     .bloom_filter(8)
+    ///     .open(&path)
+    ///     .unwrap();
+    ///
+    /// let key: &[u8] = &[1, 2, 3];
+    /// let value: &[u8] = &[4, 5];
+    ///
+    /// let mut batch = WriteBatch::new();
+    /// batch.put(key, value);
+    /// mouse_leveldb::write(&db, &mut batch).unwrap();
+    /// assert_eq!(value, mouse_leveldb::get(&db, key).unwrap().as_ref());
+    /// ```
+    pub fn compression(mut self, compression: bool) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    /// Creates a new [`Options`] configured as `self` .
+    pub fn options(&self) -> Options {
+        let mut options = Options::new();
+
+        options.set_create_if_missing(self.create_if_missing);
+        match &self.cache {
+            None => {}
+            Some(CacheSetting::Size(capacity)) => options.set_cache_size(*capacity),
+            Some(CacheSetting::Shared(cache)) => options.set_cache(cache.clone()),
+        }
+        if let Some(bits_per_key) = self.bloom_filter {
+            options.set_bloom_filter(bits_per_key);
+        }
+        options.set_compression(self.compression);
+
+        options
+    }
+
+    /// Opens the database at `path` with the [`Options`] configured as `self` .
+    pub fn open(&self, path: &CStr) -> Result<Database> {
+        let mut db = Database::new();
+        db.open_with(path, self.options())?;
+        Ok(db)
+    }
+}
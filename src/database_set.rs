@@ -0,0 +1,197 @@
+// Copyright 2021 Shin Yoshida
+//
+// "LGPL-3.0-or-later OR Apache-2.0 OR BSD-2-Clause"
+//
+// This is part of mouse-leveldb
+//
+//  mouse-leveldb is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU Lesser General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  mouse-leveldb is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU Lesser General Public License for more details.
+//
+//  You should have received a copy of the GNU Lesser General Public License
+//  along with mouse-leveldb.  If not, see <http://www.gnu.org/licenses/>.
+//
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted
+// provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of
+//    conditions and the following disclaimer.
+// 2. Redistributions in binary form must reproduce the above copyright notice, this
+//    list of conditions and the following disclaimer in the documentation and/or other
+//    materials provided with the distribution.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+// ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+// WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+// IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT,
+// INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+// NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+
+use crate::database::Database;
+use crate::database_builder::DatabaseBuilder;
+use crate::error::{self, Result};
+use std::ffi::CString;
+use std::path::Path;
+
+const NUL_IN_PATH: &str = "path includes a nul byte";
+
+/// `DatabaseSet` is a set of named [`Database`] instances stored under the same root directory.
+///
+/// Each database is stored in the directory named after the name under the root directory.
+/// The databases are closed in the reverse order of open on the drop.
+pub struct DatabaseSet {
+    databases: Vec<(String, Database)>,
+}
+
+impl Drop for DatabaseSet {
+    fn drop(&mut self) {
+        while let Some((_, mut db)) = self.databases.pop() {
+            db.close();
+        }
+    }
+}
+
+impl DatabaseSet {
+    /// Opens the databases named `names` under `root` with the options configured as `builder` .
+    ///
+    /// `root` must exist. If `builder` is configured with a [`Cache`] , all the databases share
+    /// it.
+    ///
+    /// If failed to open any database, closes the databases opened so far and returns the error
+    /// with the name of the failed database.
+    ///
+    /// It is an error if the path of any database includes a nul byte; the kind is
+    /// [`ErrorKind::InvalidArgument`] .
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::{Cache, DatabaseBuilder, DatabaseSet, ErrorKind, WriteBatch};
+    /// use tempfile;
+    ///
+    /// let tmp = tempfile::tempdir().unwrap();
+    /// let names = ["foo", "bar", "baz"];
+    /// let builder = DatabaseBuilder::new().cache(Cache::new_lru(8 << 20));
+    ///
+    /// {
+    ///     let set = DatabaseSet::open(tmp.path(), &names, &builder).unwrap();
+    ///     for name in names.iter() {
+    ///         let db = set.get(name).unwrap();
+    ///         let mut batch = WriteBatch::new();
+    ///         batch.put(b"name", name.as_bytes());
+    ///         mouse_leveldb::write(db, &mut batch).unwrap();
+    ///     }
+    ///     assert!(set.get("qux").is_none());
+    /// }
+    ///
+    /// // Reopens after closed.
+    /// let set = DatabaseSet::open(tmp.path(), &names, &builder).unwrap();
+    /// for name in names.iter() {
+    ///     let db = set.get(name).unwrap();
+    ///     let value = mouse_leveldb::get(db, b"name").unwrap();
+    ///     assert_eq!(name.as_bytes(), value.as_ref());
+    /// }
+    ///
+    /// // Fails if any database is failed to open.
+    /// drop(set);
+    /// let builder = DatabaseBuilder::new().create_if_missing(false);
+    /// let err = DatabaseSet::open(tmp.path(), &["foo", "qux"], &builder).err().unwrap();
+    /// assert!(err.to_string().contains("qux"));
+    ///
+    /// // "foo" has been closed on the failure.
+    /// DatabaseSet::open(tmp.path(), &["foo"], &builder).unwrap();
+    ///
+    /// // The name including a nul byte is invalid.
+    /// let err = DatabaseSet::open(tmp.path(), &["foo", "q\0ux"], &builder).err().unwrap();
+    /// assert_eq!(ErrorKind::InvalidArgument, err.kind());
+    /// assert!(err.to_string().contains("q\0ux"));
+    /// ```
+    ///
+    /// `root` does not have to be valid UTF-8.
+    ///
+    /// ```
+    /// # #[cfg(unix)]
+    /// # {
+    /// use mouse_leveldb::{DatabaseBuilder, DatabaseSet};
+    /// use std::ffi::OsStr;
+    /// use std::os::unix::ffi::OsStrExt;
+    /// use tempfile;
+    ///
+    /// let tmp = tempfile::tempdir().unwrap();
+    /// let root = tmp.path().join(OsStr::from_bytes(b"root-\xff"));
+    /// std::fs::create_dir(&root).unwrap();
+    ///
+    /// DatabaseSet::open(&root, &["foo"], &DatabaseBuilder::new()).unwrap();
+    /// assert!(root.join("foo").join("CURRENT").exists());
+    /// # }
+    /// ```
+    ///
+    /// [`Cache`]: crate::Cache
+    /// [`ErrorKind::InvalidArgument`]: crate::ErrorKind::InvalidArgument
+    pub fn open(root: &Path, names: &[&str], builder: &DatabaseBuilder) -> Result<Self> {
+        // 'ret' closes the opened databases on the drop if failed.
+        let mut ret = Self {
+            databases: Vec::with_capacity(names.len()),
+        };
+
+        for &name in names {
+            let mut db = Database::new();
+            let opened = to_cstring_(&root.join(name))
+                .and_then(|path| db.open_with(&path, builder.options()));
+            if let Err(e) = opened {
+                return Err(error::with_context(format!("database '{}'", name), e));
+            }
+            ret.databases.push((name.to_string(), db));
+        }
+
+        Ok(ret)
+    }
+
+    /// Returns the database named `name` if any.
+    #[inline]
+    pub fn get(&self, name: &str) -> Option<&Database> {
+        self.databases
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, db)| db)
+    }
+}
+
+#[cfg(unix)]
+fn to_cstring_(path: &Path) -> Result<CString> {
+    use std::os::unix::ffi::OsStrExt;
+
+    CString::new(path.as_os_str().as_bytes()).map_err(|_| error::invalid_argument(NUL_IN_PATH))
+}
+
+#[cfg(not(unix))]
+fn to_cstring_(path: &Path) -> Result<CString> {
+    let path = path
+        .to_str()
+        .ok_or_else(|| error::invalid_argument("path is not valid Unicode"))?;
+    CString::new(path).map_err(|_| error::invalid_argument(NUL_IN_PATH))
+}
@@ -0,0 +1,144 @@
+// Copyright 2021 Shin Yoshida
+//
+// "LGPL-3.0-or-later OR Apache-2.0 OR BSD-2-Clause"
+//
+// This is part of mouse-leveldb
+//
+//  mouse-leveldb is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU Lesser General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  mouse-leveldb is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU Lesser General Public License for more details.
+//
+//  You should have received a copy of the GNU Lesser General Public License
+//  along with mouse-leveldb.  If not, see <http://www.gnu.org/licenses/>.
+//
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted
+// provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of
+//    conditions and the following disclaimer.
+// 2. Redistributions in binary form must reproduce the above copyright notice, this
+//    list of conditions and the following disclaimer in the documentation and/or other
+//    materials provided with the distribution.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+// ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+// WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+// IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT,
+// INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+// NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+
+use std::fs;
+use std::io;
+use std::ops::AddAssign;
+use std::path::Path;
+
+/// `FileStats` is the number and the total byte size of files.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FileStats {
+    /// The number of the files.
+    pub files: u64,
+    /// The total byte size of the files.
+    pub bytes: u64,
+}
+
+impl AddAssign for FileStats {
+    fn add_assign(&mut self, other: Self) {
+        self.files += other.files;
+        self.bytes += other.bytes;
+    }
+}
+
+/// `DiskUsage` reports the files in the database directory.
+///
+/// See [`Database::disk_usage`] for details.
+///
+/// [`Database::disk_usage`]: crate::Database::disk_usage
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DiskUsage {
+    /// All the files in the database directory.
+    pub total: FileStats,
+    /// The table files. (`*.ldb` or `*.sst` .)
+    pub tables: FileStats,
+    /// The write ahead log files. (`*.log` .)
+    pub logs: FileStats,
+    /// The manifest files. (`MANIFEST-*` .)
+    pub manifests: FileStats,
+    /// The lock file. (`LOCK` .)
+    pub lock: FileStats,
+    /// The file pointing the current manifest. (`CURRENT` .)
+    pub current: FileStats,
+    /// The other files, for example, the info log files. (`LOG` and `LOG.old` .)
+    pub other: FileStats,
+}
+
+/// Walks the files in `dir` and returns the report.
+///
+/// The files removed while walking are skipped.
+pub fn walk(dir: &Path) -> io::Result<DiskUsage> {
+    let mut ret = DiskUsage::default();
+
+    for entry in fs::read_dir(dir)? {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(e),
+        };
+
+        let metadata = match entry.metadata() {
+            Ok(metadata) => metadata,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(e),
+        };
+        if !metadata.is_file() {
+            continue;
+        }
+
+        let stats = FileStats {
+            files: 1,
+            bytes: metadata.len(),
+        };
+
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        if name.ends_with(".ldb") || name.ends_with(".sst") {
+            ret.tables += stats;
+        } else if name.ends_with(".log") {
+            ret.logs += stats;
+        } else if name.starts_with("MANIFEST-") {
+            ret.manifests += stats;
+        } else if name == "LOCK" {
+            ret.lock += stats;
+        } else if name == "CURRENT" {
+            ret.current += stats;
+        } else {
+            ret.other += stats;
+        }
+        ret.total += stats;
+    }
+
+    Ok(ret)
+}
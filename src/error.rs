@@ -53,45 +53,1047 @@
 
 use core::ptr::NonNull;
 use leveldb_sys::leveldb_free;
+use std::borrow::Cow;
 use std::ffi::CStr;
 use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::io;
 use std::os::raw::{c_char, c_void};
+use std::sync::Arc;
 
-/// `Error` implements `std::error::Error` .
-pub struct Error(NonNull<c_char>);
+const NOT_OPENED: &str = "database not opened";
+const READ_ONLY: &str = "database opened read-only";
 
-unsafe impl Send for Error {}
-unsafe impl Sync for Error {}
+/// `ErrorKind` is the category of [`Error`] .
+///
+/// See [`Error::kind`] for details.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// The requested entry or file is not found.
+    NotFound,
+    /// LevelDB detected the corruption of the database.
+    Corruption,
+    /// The operation is not supported.
+    NotSupported,
+    /// The argument, for example, the options, is invalid.
+    InvalidArgument,
+    /// The lock file of the database is held by another handle or another process.
+    LockHeld,
+    /// The I/O operation failed.
+    IoError,
+    /// The database is not opened.
+    NotOpened,
+    /// The database is already opened.
+    AlreadyOpen,
+    /// The database is opened read-only and rejected the write.
+    ReadOnly,
+    /// LevelDB broke the contract of its C API, for example, returned a null pointer without any
+    /// error, or the memory allocation failed.
+    Internal,
+    /// The value does not have the length the caller expected; see [`Error::length_mismatch`] .
+    WrongLength,
+    /// Any other error.
+    Other,
+}
+
+impl ErrorKind {
+    /// Classifies `message` reported by LevelDB, for example, the message of `leveldb::Status` .
+    ///
+    /// This is the classifier [`Error::kind`] uses. The message which is not known is
+    /// [`ErrorKind::Other`] .
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::ErrorKind;
+    ///
+    /// let kind = ErrorKind::from_message(b"Corruption: bad record length");
+    /// assert_eq!(ErrorKind::Corruption, kind);
+    /// assert_eq!(ErrorKind::Other, ErrorKind::from_message(b"unknown \xff"));
+    /// ```
+    pub fn from_message(message: &[u8]) -> Self {
+        classify(message)
+    }
 
-impl Drop for Error {
-    fn drop(&mut self) {
-        unsafe { leveldb_free(self.0.as_ptr() as *mut c_void) };
+    /// Returns `true` if the operation failed with `message` reported by LevelDB may succeed by
+    /// retrying.
+    ///
+    /// This is the predicate [`Error::is_retryable`] uses. The message is retryable if the lock
+    /// is held, or if it is [`ErrorKind::IoError`] caused by a transient error; i.e. `EAGAIN` ,
+    /// `EINTR` , `EBUSY` , or `ETIMEDOUT` . The other I/O errors, for example, "No space left on
+    /// device" or "Permission denied", are not retryable.
+    ///
+    /// # Examples
+    ///
+    /// `tests/leveldb_status_messages.txt` pins whether each message is retryable.
+    ///
+    /// ```
+    /// use mouse_leveldb::ErrorKind;
+    /// use std::fs;
+    ///
+    /// let file = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/leveldb_status_messages.txt");
+    /// let corpus = fs::read_to_string(file).unwrap();
+    ///
+    /// for line in corpus.lines().filter(|line| !line.starts_with('#')) {
+    ///     let mut fields = line.splitn(3, '\t');
+    ///     let retryable = fields.nth(1).unwrap() == "1";
+    ///     let message = fields.next().unwrap();
+    ///
+    ///     let actual = ErrorKind::is_retryable_message(message.as_bytes());
+    ///     assert_eq!(retryable, actual, "{}", message);
+    /// }
+    /// ```
+    pub fn is_retryable_message(message: &[u8]) -> bool {
+        match classify(message) {
+            ErrorKind::LockHeld => true,
+            ErrorKind::IoError => is_transient_(message),
+            _ => false,
+        }
+    }
+
+    /// Returns the numeric code of `self` to log or to report to a metrics system.
+    ///
+    /// The mapping is stable; the code of each kind will never change, and a kind added in the
+    /// future will be given a new code.
+    ///
+    /// | kind                           | code |
+    /// |--------------------------------|------|
+    /// | [`ErrorKind::Other`]           | 0    |
+    /// | [`ErrorKind::NotFound`]        | 1    |
+    /// | [`ErrorKind::Corruption`]      | 2    |
+    /// | [`ErrorKind::NotSupported`]    | 3    |
+    /// | [`ErrorKind::InvalidArgument`] | 4    |
+    /// | [`ErrorKind::IoError`]         | 5    |
+    /// | [`ErrorKind::LockHeld`]        | 100  |
+    /// | [`ErrorKind::NotOpened`]       | 200  |
+    /// | [`ErrorKind::AlreadyOpen`]     | 201  |
+    /// | [`ErrorKind::ReadOnly`]        | 202  |
+    /// | [`ErrorKind::Internal`]        | 300  |
+    /// | [`ErrorKind::WrongLength`]     | 400  |
+    ///
+    /// # Examples
+    ///
+    /// `tests/leveldb_status_messages.txt` pins the codes of the messages reported by LevelDB.
+    ///
+    /// ```
+    /// use mouse_leveldb::ErrorKind;
+    /// use std::fs;
+    ///
+    /// let file = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/leveldb_status_messages.txt");
+    /// let corpus = fs::read_to_string(file).unwrap();
+    ///
+    /// for line in corpus.lines().filter(|line| !line.starts_with('#')) {
+    ///     let mut fields = line.splitn(3, '\t');
+    ///     let code: u16 = fields.next().unwrap().parse().unwrap();
+    ///     let message = fields.nth(1).unwrap();
+    ///
+    ///     assert_eq!(code, ErrorKind::from_message(message.as_bytes()).code(), "{}", message);
+    /// }
+    /// ```
+    pub fn code(self) -> u16 {
+        match self {
+            ErrorKind::Other => 0,
+            ErrorKind::NotFound => 1,
+            ErrorKind::Corruption => 2,
+            ErrorKind::NotSupported => 3,
+            ErrorKind::InvalidArgument => 4,
+            ErrorKind::IoError => 5,
+            ErrorKind::LockHeld => 100,
+            ErrorKind::NotOpened => 200,
+            ErrorKind::AlreadyOpen => 201,
+            ErrorKind::ReadOnly => 202,
+            ErrorKind::Internal => 300,
+            ErrorKind::WrongLength => 400,
+        }
     }
 }
 
-/// Creates a new instance wrapping `ptr` .
+/// `Result` is the alias of `core::result::Result` whose error type is [`Error`] .
+///
+/// # Examples
+///
+/// ```
+/// use mouse_leveldb::{Database, Result, WriteBatch};
+/// use std::ffi::CString;
+/// use tempfile;
+///
+/// fn put(db: &Database, key: &[u8], value: &[u8]) -> Result<()> {
+///     let mut batch = WriteBatch::new();
+///     batch.put(key, value);
+///     mouse_leveldb::write(db, &mut batch)?;
+///     Ok(())
+/// }
+///
+/// let tmp = tempfile::tempdir().unwrap();
+/// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+///
+/// let mut db = Database::new();
+/// db.open(&path).unwrap();
+///
+/// put(&db, &[1], &[10]).unwrap();
+/// assert_eq!(&[10], mouse_leveldb::get(&db, &[1]).unwrap().as_ref());
+/// ```
+pub type Result<T> = core::result::Result<T, Error>;
+
+/// `Error` implements `std::error::Error` .
+///
+/// `Display` shows the message, for example, the one reported by LevelDB, and `Debug` shows the
+/// context as well if any.
+///
+/// `Error` is `Send + Sync + 'static` , so it can be boxed into `Box<dyn std::error::Error + Send +
+/// Sync>` or passed to another thread.
+///
+/// # Examples
+///
+/// ```
+/// use mouse_leveldb::Database;
+/// use std::ffi::CString;
+/// use tempfile;
+///
+/// fn takes_err(e: Box<dyn std::error::Error + Send + Sync>) -> String {
+///     e.to_string()
+/// }
+///
+/// let tmp = tempfile::tempdir().unwrap();
+///
+/// // It is impossible to create a database under a regular file.
+/// let file = tmp.path().join("file");
+/// std::fs::write(&file, b"").unwrap();
+/// let path = CString::new(file.join("db").to_str().unwrap()).unwrap();
+///
+/// let mut db = Database::new();
+/// let e = db.open(&path).unwrap_err();
+///
+/// let message = e.to_string();
+/// assert_eq!(false, message.is_empty());
+/// assert_eq!(message, takes_err(Box::new(e)));
+/// ```
+///
+/// `Error` implements `Clone` , `PartialEq` , `Eq` , and `Hash` .
+///
+/// ```
+/// use mouse_leveldb::Database;
+/// use std::collections::HashSet;
+/// use std::ffi::CString;
+/// use tempfile;
+///
+/// let tmp = tempfile::tempdir().unwrap();
+/// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+///
+/// let mut db1 = Database::new();
+/// db1.open(&path).unwrap();
+///
+/// let mut db2 = Database::new();
+/// let e1 = db2.open(&path).unwrap_err();
+/// let e2 = e1.clone();
+/// assert_eq!(e1, e2);
+/// assert_eq!(e1.to_string(), e2.to_string());
+///
+/// let e3 = db2.reopen().unwrap_err();
+/// assert_ne!(e1, e3);
+///
+/// let set: HashSet<_> = vec![e1, e2, e3].into_iter().collect();
+/// assert_eq!(2, set.len());
+/// ```
+#[derive(Clone)]
+pub struct Error(Repr);
+
+// Makes sure 'Error' is 'Send + Sync' .
+const _: fn() = || {
+    fn assert_send_sync<T: Send + Sync + 'static>() {}
+    assert_send_sync::<Error>();
+};
+
+#[derive(Clone)]
+enum Repr {
+    // The message is copied byte by byte from the one generated by LevelDB, which is freed in
+    // 'new()' . It is not always UTF-8.
+    LevelDB(Box<[u8]>),
+    Retry {
+        attempts: usize,
+        last: Box<Error>,
+    },
+    Context {
+        context: String,
+        source: Box<Error>,
+    },
+    Io(Arc<io::Error>),
+    #[cfg(feature = "serde")]
+    Codec(Arc<dyn std::error::Error + Send + Sync>),
+    NotOpened,
+    AlreadyOpen(String),
+    ReadOnly,
+    InvalidArgument(&'static str),
+    NotSupported(&'static str),
+    Internal(&'static str),
+    WrongLength {
+        expected: usize,
+        actual: usize,
+    },
+}
+
+/// Creates a new instance copying the message `ptr` points to, and frees `ptr` .
 ///
 /// # Safety
 ///
 /// `ptr` must be generated by functions in crate `leveldb_sys` ; otherwise it may lead memory
 /// unsafety.
-#[inline]
-pub const unsafe fn new(ptr: NonNull<c_char>) -> Error {
-    Error(ptr)
+pub unsafe fn new(ptr: NonNull<c_char>) -> Error {
+    let msg: Box<[u8]> = CStr::from_ptr(ptr.as_ptr()).to_bytes().into();
+    leveldb_free(ptr.as_ptr() as *mut c_void);
+    Error(Repr::LevelDB(msg))
+}
+
+/// Creates a new instance to tell that the operation gave up after `attempts` trials.
+///
+/// `last` is the error of the last trial.
+pub fn retry(attempts: usize, last: Error) -> Error {
+    Error(Repr::Retry {
+        attempts,
+        last: Box::new(last),
+    })
+}
+
+/// Creates a new instance wrapping `source` with `context` , which describes where `source`
+/// occurred.
+pub fn with_context(context: String, source: Error) -> Error {
+    Error(Repr::Context {
+        context,
+        source: Box::new(source),
+    })
+}
+
+/// Creates a new instance wrapping `source` with the context telling that `source` occurred in
+/// operation `op` on the database at `path` .
+///
+/// If `key` is `Some` , the context shows the first 16 bytes of it in hex as well.
+pub fn with_operation(op: &str, path: &CStr, key: Option<&[u8]>, source: Error) -> Error {
+    const KEY_PREVIEW_LEN: usize = 16;
+
+    let mut context = format!("{} on {}", op, path.to_string_lossy());
+    if let Some(key) = key {
+        context.push_str(" (key=0x");
+        for b in key.iter().take(KEY_PREVIEW_LEN) {
+            context.push_str(&format!("{:02x}", b));
+        }
+        if KEY_PREVIEW_LEN < key.len() {
+            context.push('…');
+        }
+        context.push(')');
+    }
+
+    with_context(context, source)
+}
+
+/// Creates a new instance wrapping `e` .
+pub fn io(e: io::Error) -> Error {
+    Error(Repr::Io(Arc::new(e)))
+}
+
+/// Creates a new instance wrapping `e` , which failed to encode or decode a key or a value.
+#[cfg(feature = "serde")]
+pub fn codec(e: Box<dyn std::error::Error + Send + Sync>) -> Error {
+    Error(Repr::Codec(Arc::from(e)))
+}
+
+/// Creates a new instance to tell that the database has never been opened.
+pub fn not_opened() -> Error {
+    Error(Repr::NotOpened)
+}
+
+/// Creates a new instance to tell that the database is already opened at `path` .
+pub fn already_open(path: &CStr) -> Error {
+    Error(Repr::AlreadyOpen(path.to_string_lossy().into_owned()))
+}
+
+/// Creates a new instance to tell that the database is opened to read only.
+pub fn read_only() -> Error {
+    Error(Repr::ReadOnly)
+}
+
+/// Creates a new instance to tell that the argument is invalid.
+///
+/// `what` describes why the argument is invalid.
+pub fn invalid_argument(what: &'static str) -> Error {
+    Error(Repr::InvalidArgument(what))
+}
+
+/// Creates a new instance to tell that the operation is not supported.
+///
+/// `what` describes the operation and the alternative if any.
+pub fn not_supported(what: &'static str) -> Error {
+    Error(Repr::NotSupported(what))
+}
+
+/// Creates a new instance to tell that LevelDB broke the contract of its C API.
+///
+/// `what` describes the broken contract.
+pub fn internal(what: &'static str) -> Error {
+    Error(Repr::Internal(what))
+}
+
+/// Creates a new instance to tell that the value is `actual` bytes long while `expected` bytes
+/// are expected.
+pub fn wrong_length(expected: usize, actual: usize) -> Error {
+    Error(Repr::WrongLength { expected, actual })
+}
+
+impl Error {
+    /// Returns how many times the operation was tried before failing.
+    ///
+    /// It is always 1 unless the error is returned from [`Database::open_with_retry`] .
+    ///
+    /// [`Database::open_with_retry`]: crate::Database::open_with_retry
+    pub fn attempts(&self) -> usize {
+        match &self.0 {
+            Repr::Retry { attempts, .. } => *attempts,
+            Repr::Context { source, .. } => source.attempts(),
+            _ => 1,
+        }
+    }
+
+    /// Returns `true` if `self` tells that the operation was rejected because the database is
+    /// opened by [`Database::open_read_only`] .
+    ///
+    /// This is same to `self.kind() == ErrorKind::ReadOnly` .
+    ///
+    /// [`Database::open_read_only`]: crate::Database::open_read_only
+    #[inline]
+    pub fn is_read_only(&self) -> bool {
+        self.kind() == ErrorKind::ReadOnly
+    }
+
+    /// Returns `(expected, actual)` length if `self` tells that the value does not have the
+    /// expected length; otherwise returns `None` .
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::{ErrorKind, Octets};
+    ///
+    /// let e = Octets::from(vec![1, 2, 3]).to_array::<8>().unwrap_err();
+    /// assert_eq!(ErrorKind::WrongLength, e.kind());
+    /// assert_eq!(Some((8, 3)), e.length_mismatch());
+    /// assert_eq!("wrong length: expected 8 bytes, got 3", e.to_string());
+    /// ```
+    pub fn length_mismatch(&self) -> Option<(usize, usize)> {
+        match &self.0 {
+            Repr::WrongLength { expected, actual } => Some((*expected, *actual)),
+            Repr::Retry { last, .. } => last.length_mismatch(),
+            Repr::Context { source, .. } => source.length_mismatch(),
+            _ => None,
+        }
+    }
+
+    /// Returns the category of `self` .
+    ///
+    /// The category of the error reported by LevelDB is derived from the prefix of the message as
+    /// follows. The message itself is still available via `Display` .
+    ///
+    /// | Message                                         | `ErrorKind`                    |
+    /// |-------------------------------------------------|--------------------------------|
+    /// | `NotFound: ...`                                 | [`ErrorKind::NotFound`]        |
+    /// | `Corruption: ...`                               | [`ErrorKind::Corruption`]      |
+    /// | `Not implemented: ...`                          | [`ErrorKind::NotSupported`]    |
+    /// | `Invalid argument: ...`                         | [`ErrorKind::InvalidArgument`] |
+    /// | `IO error: lock /path/to/LOCK: ...`             | [`ErrorKind::LockHeld`]        |
+    /// | `IO error: ...`                                 | [`ErrorKind::IoError`]         |
+    /// | others                                          | [`ErrorKind::Other`]           |
+    ///
+    /// The error telling that the database is not opened is [`ErrorKind::NotOpened`] , the one
+    /// telling that the database is already opened is [`ErrorKind::AlreadyOpen`] , and the one
+    /// telling that the write is rejected by the read-only database is [`ErrorKind::ReadOnly`] .
+    /// The error telling that LevelDB broke the contract of its C API is [`ErrorKind::Internal`] ,
+    /// and the one telling that the value has an unexpected length is [`ErrorKind::WrongLength`] .
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::{Database, ErrorKind, Options};
+    /// use std::ffi::CString;
+    /// use tempfile;
+    ///
+    /// let tmp = tempfile::tempdir().unwrap();
+    /// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+    ///
+    /// // Another handle holds the lock.
+    /// let mut db1 = Database::new();
+    /// db1.open(&path).unwrap();
+    ///
+    /// let mut db2 = Database::new();
+    /// let e = db2.open(&path).unwrap_err();
+    /// assert_eq!(ErrorKind::LockHeld, e.kind());
+    ///
+    /// // The database does not exist.
+    /// let missing = CString::new(tmp.path().join("missing").to_str().unwrap()).unwrap();
+    /// let mut options = Options::new();
+    /// options.set_create_if_missing(false);
+    ///
+    /// let mut db3 = Database::new();
+    /// let e = db3.open_with(&missing, options).unwrap_err();
+    /// assert_eq!(ErrorKind::InvalidArgument, e.kind());
+    /// ```
+    pub fn kind(&self) -> ErrorKind {
+        match &self.0 {
+            Repr::LevelDB(msg) => classify(msg),
+            Repr::Retry { last, .. } => last.kind(),
+            Repr::Context { source, .. } => source.kind(),
+            Repr::Io(_) => ErrorKind::IoError,
+            Repr::NotOpened => ErrorKind::NotOpened,
+            Repr::AlreadyOpen(_) => ErrorKind::AlreadyOpen,
+            Repr::ReadOnly => ErrorKind::ReadOnly,
+            Repr::InvalidArgument(_) => ErrorKind::InvalidArgument,
+            Repr::NotSupported(_) => ErrorKind::NotSupported,
+            Repr::Internal(_) => ErrorKind::Internal,
+            Repr::WrongLength { .. } => ErrorKind::WrongLength,
+            #[cfg(feature = "serde")]
+            Repr::Codec(_) => ErrorKind::Other,
+        }
+    }
+
+    /// Returns the numeric code of the kind of `self` .
+    ///
+    /// See [`ErrorKind::code`] for the mapping.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::{Database, WriteBatch};
+    /// use std::ffi::CString;
+    /// use tempfile;
+    ///
+    /// let tmp = tempfile::tempdir().unwrap();
+    /// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+    ///
+    /// let mut db1 = Database::new();
+    /// db1.open(&path).unwrap();
+    ///
+    /// // Lock held.
+    /// let mut db2 = Database::new();
+    /// assert_eq!(100, db2.open(&path).unwrap_err().code());
+    ///
+    /// // Not opened.
+    /// assert_eq!(200, mouse_leveldb::get(&db2, b"key").unwrap_err().code());
+    ///
+    /// // Already opened.
+    /// assert_eq!(201, db1.open(&path).unwrap_err().code());
+    ///
+    /// // Write on the read-only database.
+    /// db1.close();
+    /// db1.open_read_only(&path).unwrap();
+    ///
+    /// let mut batch = WriteBatch::new();
+    /// batch.put(b"key", b"value");
+    /// assert_eq!(202, mouse_leveldb::write(&db1, &mut batch).unwrap_err().code());
+    /// ```
+    #[inline]
+    pub fn code(&self) -> u16 {
+        self.kind().code()
+    }
+
+    /// Returns the message reported by LevelDB without the context, or `None` if `self` is not
+    /// reported by LevelDB.
+    ///
+    /// The bytes which are not valid UTF-8 are replaced with `U+FFFD` ; use
+    /// [`message_bytes`](#method.message_bytes) to get the message as it is.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::{Database, Options};
+    /// use std::ffi::CString;
+    /// use tempfile;
+    ///
+    /// let tmp = tempfile::tempdir().unwrap();
+    /// let path = CString::new(tmp.path().join("missing").to_str().unwrap()).unwrap();
+    ///
+    /// let mut options = Options::new();
+    /// options.set_create_if_missing(false);
+    ///
+    /// let mut db = Database::new();
+    /// let e = db.open_with(&path, options).unwrap_err();
+    ///
+    /// let message = e.leveldb_message().unwrap();
+    /// assert_eq!(true, message.starts_with("Invalid argument: "));
+    /// assert_eq!(format!("open on {}: {}", path.to_str().unwrap(), message), e.to_string());
+    /// ```
+    pub fn leveldb_message(&self) -> Option<Cow<'_, str>> {
+        match &self.0 {
+            Repr::LevelDB(msg) => Some(String::from_utf8_lossy(msg)),
+            Repr::Retry { last, .. } => last.leveldb_message(),
+            Repr::Context { source, .. } => source.leveldb_message(),
+            _ => None,
+        }
+    }
+
+    /// Returns the message reported by LevelDB as it is, without the context.
+    ///
+    /// LevelDB does not guarantee the message is UTF-8; for example, it includes the path to
+    /// the database, which can be any bytes.
+    /// Returns an empty slice if `self` is not reported by LevelDB.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::{Database, Options};
+    /// use std::ffi::CString;
+    /// use tempfile;
+    ///
+    /// let tmp = tempfile::tempdir().unwrap();
+    /// let mut path = tmp.path().to_str().unwrap().as_bytes().to_vec();
+    /// path.extend_from_slice(b"/missing-\xff\xfe");
+    /// let path = CString::new(path).unwrap();
+    ///
+    /// let mut options = Options::new();
+    /// options.set_create_if_missing(false);
+    ///
+    /// let mut db = Database::new();
+    /// let e = db.open_with(&path, options).unwrap_err();
+    ///
+    /// // The invalid UTF-8 bytes are kept as they are.
+    /// let bytes = e.message_bytes();
+    /// assert_eq!(true, bytes.starts_with(b"Invalid argument: "));
+    /// assert_eq!(true, bytes.windows(10).any(|w| w == b"missing-\xff\xfe"));
+    ///
+    /// // 'message()' and 'Display' replace them with U+FFFD .
+    /// let message = e.message();
+    /// assert_eq!(String::from_utf8_lossy(bytes), message);
+    /// assert_eq!(true, message.contains("missing-\u{fffd}\u{fffd}"));
+    /// assert_eq!(true, e.to_string().ends_with(message.as_ref()));
+    ///
+    /// // Not reported by LevelDB.
+    /// let e = mouse_leveldb::get(&Database::new(), b"key").unwrap_err();
+    /// assert_eq!(true, e.message_bytes().is_empty());
+    /// ```
+    pub fn message_bytes(&self) -> &[u8] {
+        match &self.0 {
+            Repr::LevelDB(msg) => msg,
+            Repr::Retry { last, .. } => last.message_bytes(),
+            Repr::Context { source, .. } => source.message_bytes(),
+            _ => &[],
+        }
+    }
+
+    /// Returns the message without the context, replacing the bytes which are not valid UTF-8
+    /// with `U+FFFD` .
+    ///
+    /// If `self` is reported by LevelDB, the message is the same as
+    /// [`message_bytes`](#method.message_bytes) except for the replacement; otherwise, it is
+    /// the text of `self` which `Display` shows.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::Database;
+    ///
+    /// let db = Database::new();
+    /// let e = mouse_leveldb::get(&db, b"key").unwrap_err();
+    /// assert_eq!(e.to_string(), e.message());
+    /// ```
+    pub fn message(&self) -> Cow<'_, str> {
+        match &self.0 {
+            Repr::LevelDB(msg) => String::from_utf8_lossy(msg),
+            Repr::Retry { last, .. } => last.message(),
+            Repr::Context { source, .. } => source.message(),
+            _ => Cow::Owned(self.to_string()),
+        }
+    }
+
+    /// Returns `true` if the kind of `self` is [`ErrorKind::Corruption`] .
+    #[inline]
+    pub fn is_corruption(&self) -> bool {
+        self.kind() == ErrorKind::Corruption
+    }
+
+    /// Returns `true` if the kind of `self` is [`ErrorKind::IoError`] .
+    ///
+    /// Note that it is `false` if the kind is [`ErrorKind::LockHeld`] .
+    #[inline]
+    pub fn is_io(&self) -> bool {
+        self.kind() == ErrorKind::IoError
+    }
+
+    /// Returns `true` if the kind of `self` is [`ErrorKind::LockHeld`] .
+    #[inline]
+    pub fn is_lock_held(&self) -> bool {
+        self.kind() == ErrorKind::LockHeld
+    }
+
+    /// Returns `true` if the kind of `self` is [`ErrorKind::NotFound`] .
+    #[inline]
+    pub fn is_not_found(&self) -> bool {
+        self.kind() == ErrorKind::NotFound
+    }
+
+    /// Returns `true` if the operation may succeed by retrying; i.e. if `self` tells that the lock
+    /// is held or a transient I/O error.
+    ///
+    /// The errors reported by LevelDB are judged by [`ErrorKind::is_retryable_message`] . The
+    /// I/O errors like "No space left on device" are not retryable, nor are the other kinds, for
+    /// example, the corruption, the invalid argument, and the errors synthesized by this crate
+    /// like "database not opened".
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::Database;
+    /// use std::ffi::CString;
+    /// use tempfile;
+    ///
+    /// let tmp = tempfile::tempdir().unwrap();
+    /// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+    ///
+    /// // "database not opened"
+    /// let mut db1 = Database::new();
+    /// let e = db1.reopen().unwrap_err();
+    /// assert_eq!(false, e.is_corruption());
+    /// assert_eq!(false, e.is_io());
+    /// assert_eq!(false, e.is_lock_held());
+    /// assert_eq!(false, e.is_not_found());
+    /// assert_eq!(false, e.is_retryable());
+    ///
+    /// // Another handle holds the lock.
+    /// db1.open(&path).unwrap();
+    /// let mut db2 = Database::new();
+    /// let e = db2.open(&path).unwrap_err();
+    /// assert_eq!(true, e.is_lock_held());
+    /// assert_eq!(false, e.is_io());
+    /// assert_eq!(true, e.is_retryable());
+    /// ```
+    pub fn is_retryable(&self) -> bool {
+        match &self.0 {
+            Repr::Io(e) => matches!(
+                e.kind(),
+                io::ErrorKind::Interrupted | io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+            ),
+            Repr::Retry { last, .. } => last.is_retryable(),
+            Repr::Context { source, .. } => source.is_retryable(),
+            Repr::LevelDB(msg) => ErrorKind::is_retryable_message(msg),
+            _ => false,
+        }
+    }
+
+    /// Converts `self` into `std::io::Error` .
+    ///
+    /// The kind is mapped as follows, and `source()` of the returned error is `self` . If `self`
+    /// wraps `std::io::Error` , returns it as it is.
+    ///
+    /// | `ErrorKind`                    | `std::io::ErrorKind` |
+    /// |--------------------------------|----------------------|
+    /// | [`ErrorKind::NotFound`]        | `NotFound`           |
+    /// | [`ErrorKind::Corruption`]      | `InvalidData`        |
+    /// | [`ErrorKind::NotSupported`]    | `Unsupported`        |
+    /// | [`ErrorKind::InvalidArgument`] | `InvalidInput`       |
+    /// | [`ErrorKind::LockHeld`]        | `WouldBlock`         |
+    /// | [`ErrorKind::NotOpened`]       | `NotConnected`       |
+    /// | [`ErrorKind::AlreadyOpen`]     | `AlreadyExists`      |
+    /// | [`ErrorKind::ReadOnly`]        | `PermissionDenied`   |
+    /// | [`ErrorKind::WrongLength`]     | `InvalidData`        |
+    /// | [`ErrorKind::Internal`]        | `Other`              |
+    /// | [`ErrorKind::IoError`]         | `Other`              |
+    /// | [`ErrorKind::Other`]           | `Other`              |
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::{Database, Error};
+    /// use std::error::Error as _;
+    /// use std::ffi::CString;
+    /// use std::io;
+    /// use tempfile;
+    ///
+    /// let tmp = tempfile::tempdir().unwrap();
+    /// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+    ///
+    /// // Reported by LevelDB.
+    /// let mut db1 = Database::new();
+    /// db1.open(&path).unwrap();
+    ///
+    /// let mut db2 = Database::new();
+    /// let e = db2.open(&path).unwrap_err();
+    /// let message = e.to_string();
+    ///
+    /// let e = e.into_io();
+    /// assert_eq!(io::ErrorKind::WouldBlock, e.kind());
+    /// assert_eq!(message, e.to_string());
+    /// assert_eq!(message, e.source().unwrap().to_string());
+    ///
+    /// let source = e.source().unwrap().downcast_ref::<Error>().unwrap();
+    /// assert_eq!(true, source.is_lock_held());
+    ///
+    /// // Synthesized by this crate.
+    /// let e = mouse_leveldb::get(&db2, b"key").unwrap_err();
+    /// let message = e.to_string();
+    ///
+    /// let e = io::Error::from(e);
+    /// assert_eq!(io::ErrorKind::NotConnected, e.kind());
+    /// assert_eq!(message, e.source().unwrap().to_string());
+    /// ```
+    pub fn into_io(self) -> io::Error {
+        let kind = match self.kind() {
+            ErrorKind::NotFound => io::ErrorKind::NotFound,
+            ErrorKind::Corruption | ErrorKind::WrongLength => io::ErrorKind::InvalidData,
+            ErrorKind::NotSupported => io::ErrorKind::Unsupported,
+            ErrorKind::InvalidArgument => io::ErrorKind::InvalidInput,
+            ErrorKind::LockHeld => io::ErrorKind::WouldBlock,
+            ErrorKind::NotOpened => io::ErrorKind::NotConnected,
+            ErrorKind::AlreadyOpen => io::ErrorKind::AlreadyExists,
+            ErrorKind::ReadOnly => io::ErrorKind::PermissionDenied,
+            ErrorKind::IoError | ErrorKind::Internal | ErrorKind::Other => io::ErrorKind::Other,
+        };
+
+        match self.0 {
+            Repr::Io(e) => match Arc::try_unwrap(e) {
+                Ok(e) => e,
+                Err(e) => io::Error::new(e.kind(), IoSource(Error(Repr::Io(e)))),
+            },
+            _ => io::Error::new(kind, IoSource(self)),
+        }
+    }
+}
+
+/// `IoSource` wraps [`Error`] in `std::io::Error` .
+///
+/// `std::io::Error::source` returns `source()` of the wrapped error, so `IoSource` returns the
+/// original error from `source()` .
+struct IoSource(Error);
+
+impl fmt::Debug for IoSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl fmt::Display for IoSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl std::error::Error for IoSource {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.0)
+    }
+}
+
+/// Derives [`ErrorKind`] from the message reported by LevelDB.
+///
+/// The prefixes are compared ignoring the ASCII case and the leading white spaces.
+fn classify(msg: &[u8]) -> ErrorKind {
+    let trim_start =
+        |bytes: &[u8]| -> usize { bytes.iter().take_while(|b| b.is_ascii_whitespace()).count() };
+    let msg = &msg[trim_start(msg)..];
+    let starts_with = |prefix: &str| {
+        let prefix = prefix.as_bytes();
+        msg.len() >= prefix.len() && msg[..prefix.len()].eq_ignore_ascii_case(prefix)
+    };
+
+    if starts_with("IO error:") {
+        // LevelDB reports the error like "IO error: lock /path/to/db/LOCK: ..."
+        let rest = &msg["IO error:".len()..];
+        let rest = &rest[trim_start(rest)..];
+        if rest.starts_with(b"lock ") && rest.windows(4).any(|w| w == b"LOCK") {
+            ErrorKind::LockHeld
+        } else {
+            ErrorKind::IoError
+        }
+    } else if starts_with("NotFound:") {
+        ErrorKind::NotFound
+    } else if starts_with("Corruption:") {
+        ErrorKind::Corruption
+    } else if starts_with("Not implemented:") {
+        ErrorKind::NotSupported
+    } else if starts_with("Invalid argument:") {
+        ErrorKind::InvalidArgument
+    } else {
+        ErrorKind::Other
+    }
+}
+
+/// Returns `true` if the I/O error message `msg` tells a transient error.
+fn is_transient_(msg: &[u8]) -> bool {
+    // The text of 'strerror()' LevelDB appends to the message.
+    const TRANSIENT: &[&[u8]] = &[
+        b"Resource temporarily unavailable",
+        b"Interrupted system call",
+        b"Device or resource busy",
+        b"Connection timed out",
+    ];
+
+    TRANSIENT
+        .iter()
+        .any(|t| msg.windows(t.len()).any(|w| w == *t))
 }
 
 impl fmt::Debug for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let msg = unsafe { CStr::from_ptr(self.0.as_ptr()).to_str().unwrap() };
-        f.debug_tuple("Error").field(&msg).finish()
+        match &self.0 {
+            Repr::LevelDB(msg) => f
+                .debug_tuple("Error")
+                .field(&String::from_utf8_lossy(msg))
+                .finish(),
+            Repr::Retry { attempts, last } => f
+                .debug_struct("Error")
+                .field("attempts", attempts)
+                .field("last", last)
+                .finish(),
+            Repr::Context { context, source } => f
+                .debug_struct("Error")
+                .field("context", context)
+                .field("source", source)
+                .finish(),
+            Repr::Io(e) => f.debug_tuple("Error").field(e).finish(),
+            #[cfg(feature = "serde")]
+            Repr::Codec(e) => f.debug_tuple("Error").field(e).finish(),
+            Repr::NotOpened => f.debug_tuple("Error").field(&NOT_OPENED).finish(),
+            Repr::AlreadyOpen(path) => f.debug_struct("Error").field("already_open", path).finish(),
+            Repr::ReadOnly => f.debug_tuple("Error").field(&READ_ONLY).finish(),
+            Repr::InvalidArgument(what) => f
+                .debug_struct("Error")
+                .field("invalid_argument", what)
+                .finish(),
+            Repr::NotSupported(what) => f
+                .debug_struct("Error")
+                .field("not_supported", what)
+                .finish(),
+            Repr::Internal(what) => f.debug_struct("Error").field("internal", what).finish(),
+            Repr::WrongLength { expected, actual } => f
+                .debug_struct("Error")
+                .field("expected_len", expected)
+                .field("actual_len", actual)
+                .finish(),
+        }
     }
 }
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let msg = unsafe { CStr::from_ptr(self.0.as_ptr()).to_str().unwrap() };
-        msg.fmt(f)
+        match &self.0 {
+            Repr::LevelDB(msg) => String::from_utf8_lossy(msg).fmt(f),
+            Repr::Retry { attempts, last } => {
+                write!(f, "{} (gave up after {} attempts)", last, attempts)
+            }
+            Repr::Context { context, source } => write!(f, "{}: {}", context, source),
+            Repr::Io(e) => e.fmt(f),
+            #[cfg(feature = "serde")]
+            Repr::Codec(e) => e.fmt(f),
+            Repr::NotOpened => NOT_OPENED.fmt(f),
+            Repr::AlreadyOpen(path) => write!(f, "database already opened at {}", path),
+            Repr::ReadOnly => READ_ONLY.fmt(f),
+            Repr::InvalidArgument(what) => write!(f, "invalid argument: {}", what),
+            Repr::NotSupported(what) => write!(f, "not supported: {}", what),
+            Repr::Internal(what) => write!(f, "internal error: {}", what),
+            Repr::WrongLength { expected, actual } => write!(
+                f,
+                "wrong length: expected {} bytes, got {}",
+                expected, actual
+            ),
+        }
+    }
+}
+
+impl PartialEq for Error {
+    /// Compares the kind and the message of the errors from crate `std` or the codec; compares
+    /// the other errors by the content.
+    fn eq(&self, other: &Self) -> bool {
+        match (&self.0, &other.0) {
+            (Repr::LevelDB(a), Repr::LevelDB(b)) => a == b,
+            (
+                Repr::Retry {
+                    attempts: a,
+                    last: x,
+                },
+                Repr::Retry {
+                    attempts: b,
+                    last: y,
+                },
+            ) => a == b && x == y,
+            (
+                Repr::Context {
+                    context: a,
+                    source: x,
+                },
+                Repr::Context {
+                    context: b,
+                    source: y,
+                },
+            ) => a == b && x == y,
+            (Repr::Io(a), Repr::Io(b)) => a.kind() == b.kind() && a.to_string() == b.to_string(),
+            #[cfg(feature = "serde")]
+            (Repr::Codec(a), Repr::Codec(b)) => a.to_string() == b.to_string(),
+            (Repr::NotOpened, Repr::NotOpened) => true,
+            (Repr::AlreadyOpen(a), Repr::AlreadyOpen(b)) => a == b,
+            (Repr::ReadOnly, Repr::ReadOnly) => true,
+            (Repr::InvalidArgument(a), Repr::InvalidArgument(b)) => a == b,
+            (Repr::NotSupported(a), Repr::NotSupported(b)) => a == b,
+            (Repr::Internal(a), Repr::Internal(b)) => a == b,
+            (
+                Repr::WrongLength {
+                    expected: a,
+                    actual: x,
+                },
+                Repr::WrongLength {
+                    expected: b,
+                    actual: y,
+                },
+            ) => a == b && x == y,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for Error {}
+
+impl Hash for Error {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        core::mem::discriminant(&self.0).hash(state);
+        match &self.0 {
+            Repr::LevelDB(msg) => msg.hash(state),
+            Repr::Retry { attempts, last } => {
+                attempts.hash(state);
+                last.hash(state);
+            }
+            Repr::Context { context, source } => {
+                context.hash(state);
+                source.hash(state);
+            }
+            Repr::Io(e) => {
+                e.kind().hash(state);
+                e.to_string().hash(state);
+            }
+            #[cfg(feature = "serde")]
+            Repr::Codec(e) => e.to_string().hash(state),
+            Repr::AlreadyOpen(path) => path.hash(state),
+            Repr::InvalidArgument(what) | Repr::NotSupported(what) | Repr::Internal(what) => {
+                what.hash(state)
+            }
+            Repr::WrongLength { expected, actual } => {
+                expected.hash(state);
+                actual.hash(state);
+            }
+            Repr::NotOpened | Repr::ReadOnly => (),
+        }
+    }
+}
+
+impl From<Error> for io::Error {
+    /// Converts `e` as [`Error::into_io`] does.
+    #[inline]
+    fn from(e: Error) -> Self {
+        e.into_io()
     }
 }
 
-impl std::error::Error for Error {}
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match &self.0 {
+            Repr::Retry { last, .. } => Some(last.as_ref()),
+            Repr::Context { source, .. } => Some(source.as_ref()),
+            Repr::Io(e) => Some(e.as_ref()),
+            #[cfg(feature = "serde")]
+            Repr::Codec(e) => Some(e.as_ref()),
+            _ => None,
+        }
+    }
+}
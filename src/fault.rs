@@ -0,0 +1,146 @@
+// Copyright 2021 Shin Yoshida
+//
+// "LGPL-3.0-or-later OR Apache-2.0 OR BSD-2-Clause"
+//
+// This is part of mouse-leveldb
+//
+//  mouse-leveldb is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU Lesser General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  mouse-leveldb is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU Lesser General Public License for more details.
+//
+//  You should have received a copy of the GNU Lesser General Public License
+//  along with mouse-leveldb.  If not, see <http://www.gnu.org/licenses/>.
+//
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted
+// provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of
+//    conditions and the following disclaimer.
+// 2. Redistributions in binary form must reproduce the above copyright notice, this
+//    list of conditions and the following disclaimer in the documentation and/or other
+//    materials provided with the distribution.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+// ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+// WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+// IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT,
+// INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+// NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+
+use core::marker::PhantomData;
+use std::cell::Cell;
+
+/// `Fault` is a failure of the C API of LevelDB or of the memory allocation, which
+/// [`inject_fault`] simulates to test the error handling.
+///
+/// This is available only if feature `testing` is enabled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Fault {
+    /// `leveldb_open` returns a null pointer without any error.
+    OpenNullHandle,
+    /// `malloc` fails to allocate the buffer to copy bytes into [`Octets`] .
+    ///
+    /// [`Octets`]: crate::Octets
+    AllocationFailure,
+}
+
+thread_local! {
+    static INJECTED: Cell<Option<Fault>> = const { Cell::new(None) };
+}
+
+/// `FaultGuard` keeps [`Fault`] injected until dropped.
+///
+/// This is available only if feature `testing` is enabled.
+#[must_use = "the fault is cleared as soon as the guard is dropped"]
+pub struct FaultGuard {
+    // The fault is injected only into the current thread.
+    _not_send: PhantomData<*const ()>,
+}
+
+impl Drop for FaultGuard {
+    fn drop(&mut self) {
+        INJECTED.with(|injected| injected.set(None));
+    }
+}
+
+/// Makes the current thread simulate `fault` until the returned guard is dropped.
+///
+/// The fault injected before is replaced.
+/// This is available only if feature `testing` is enabled.
+///
+/// # Examples
+///
+/// ```
+/// use mouse_leveldb::{Database, ErrorKind, Fault};
+/// use std::ffi::CString;
+/// use tempfile;
+///
+/// let tmp = tempfile::tempdir().unwrap();
+/// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+///
+/// let mut db = Database::new();
+///
+/// let guard = mouse_leveldb::inject_fault(Fault::OpenNullHandle);
+/// assert_eq!(ErrorKind::Internal, db.open(&path).unwrap_err().kind());
+/// assert_eq!(None, db.path());
+///
+/// drop(guard);
+/// db.open(&path).unwrap();
+/// ```
+///
+/// The methods copying the bytes into [`Octets`] return an error if the allocation fails.
+///
+/// ```
+/// use mouse_leveldb::{ErrorKind, Fault, TestDb, WriteBatch};
+///
+/// let db = TestDb::new().unwrap();
+///
+/// let mut batch = WriteBatch::new();
+/// batch.put(&[1], &[10]);
+/// mouse_leveldb::write(&db, &mut batch).unwrap();
+///
+/// let guard = mouse_leveldb::inject_fault(Fault::AllocationFailure);
+/// assert_eq!(ErrorKind::Internal, db.first_key_value().unwrap_err().kind());
+/// assert_eq!(ErrorKind::Internal, db.last_key_value().unwrap_err().kind());
+///
+/// drop(guard);
+/// assert_eq!(true, db.first_key_value().unwrap().is_some());
+/// ```
+///
+/// [`Octets`]: crate::Octets
+pub fn inject_fault(fault: Fault) -> FaultGuard {
+    INJECTED.with(|injected| injected.set(Some(fault)));
+    FaultGuard {
+        _not_send: PhantomData,
+    }
+}
+
+/// Returns `true` if `fault` is injected into the current thread.
+pub fn is_injected(fault: Fault) -> bool {
+    INJECTED.with(|injected| injected.get() == Some(fault))
+}
@@ -0,0 +1,246 @@
+// Copyright 2021 Shin Yoshida
+//
+// "LGPL-3.0-or-later OR Apache-2.0 OR BSD-2-Clause"
+//
+// This is part of mouse-leveldb
+//
+//  mouse-leveldb is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU Lesser General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  mouse-leveldb is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU Lesser General Public License for more details.
+//
+//  You should have received a copy of the GNU Lesser General Public License
+//  along with mouse-leveldb.  If not, see <http://www.gnu.org/licenses/>.
+//
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted
+// provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of
+//    conditions and the following disclaimer.
+// 2. Redistributions in binary form must reproduce the above copyright notice, this
+//    list of conditions and the following disclaimer in the documentation and/or other
+//    materials provided with the distribution.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+// ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+// WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+// IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT,
+// INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+// NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+
+use crate::error::{Error, Result};
+use crate::iter::Iter;
+use std::time::{Duration, Instant};
+
+/// `IntegritySource` is the source of the (key, value) pairs that [`IntegrityReport::scan`]
+/// reads.
+///
+/// The methods behave as those of [`Iter`] of the same names; in particular, the source is
+/// expected to skip the range it failed to read and to report the error by `status` .
+pub trait IntegritySource {
+    /// Moves to the first (key, value) pair.
+    fn seek_to_first(&mut self);
+
+    /// Moves to the next (key, value) pair.
+    fn seek_next(&mut self);
+
+    /// Returns the key at the current position, or `None` if the source is exhausted.
+    fn key(&self) -> Option<&[u8]>;
+
+    /// Returns the value at the current position, or `None` if the source is exhausted.
+    fn value(&self) -> Option<&[u8]>;
+
+    /// Returns the error found so far if any.
+    fn status(&self) -> Result<()>;
+}
+
+impl IntegritySource for Iter<'_> {
+    #[inline]
+    fn seek_to_first(&mut self) {
+        Iter::seek_to_first(self)
+    }
+
+    #[inline]
+    fn seek_next(&mut self) {
+        Iter::seek_next(self)
+    }
+
+    #[inline]
+    fn key(&self) -> Option<&[u8]> {
+        Iter::key(self)
+    }
+
+    #[inline]
+    fn value(&self) -> Option<&[u8]> {
+        Iter::value(self)
+    }
+
+    #[inline]
+    fn status(&self) -> Result<()> {
+        Iter::status(self)
+    }
+}
+
+/// `IntegrityError` is an error found while scanning the database.
+#[derive(Debug)]
+pub struct IntegrityError {
+    /// The last key read successfully before the error, or `None` if the error occurred before
+    /// reading any key.
+    pub after_key: Option<Vec<u8>>,
+    /// The error reported by LevelDB.
+    pub error: Error,
+}
+
+/// `IntegrityReport` is the result of scanning the whole database.
+///
+/// See [`Database::verify_integrity`] for details.
+///
+/// [`Database::verify_integrity`]: crate::Database::verify_integrity
+#[derive(Debug)]
+pub struct IntegrityReport {
+    /// The number of the keys read successfully.
+    pub keys: u64,
+    /// The total byte size of the keys and the values read successfully.
+    pub bytes: u64,
+    /// The time taken to scan.
+    pub elapsed: Duration,
+    /// The errors found while scanning, in the order of the keys.
+    pub errors: Vec<IntegrityError>,
+}
+
+impl IntegrityReport {
+    /// Returns `true` if no error was found.
+    #[inline]
+    pub fn is_ok(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Reads all the (key, value) pairs from `source` and returns the report.
+    ///
+    /// `source.status()` is checked after every move, and a new error is recorded with the key
+    /// read just before it. The scan continues while `source` has a (key, value) pair; the error
+    /// is recorded only once while `source` keeps reporting it. (LevelDB iterator keeps the first
+    /// error until it is destroyed.)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::{Database, Error, IntegrityReport, IntegritySource, Result};
+    ///
+    /// // Fails to read keys 3 and 4, and keeps reporting the error after that.
+    /// struct Fake {
+    ///     keys: Vec<[u8; 1]>,
+    ///     pos: usize,
+    ///     error: Option<Error>,
+    /// }
+    ///
+    /// impl IntegritySource for Fake {
+    ///     fn seek_to_first(&mut self) {
+    ///         self.pos = 0;
+    ///     }
+    ///
+    ///     fn seek_next(&mut self) {
+    ///         self.pos += 1;
+    ///         if self.keys.get(self.pos) == Some(&[3]) {
+    ///             // The error is not public to create; borrow one.
+    ///             self.error = Database::new().verify().err();
+    ///             self.pos += 2;
+    ///         }
+    ///     }
+    ///
+    ///     fn key(&self) -> Option<&[u8]> {
+    ///         self.keys.get(self.pos).map(|k| &k[..])
+    ///     }
+    ///
+    ///     fn value(&self) -> Option<&[u8]> {
+    ///         self.key()
+    ///     }
+    ///
+    ///     fn status(&self) -> Result<()> {
+    ///         self.error.clone().map_or(Ok(()), Err)
+    ///     }
+    /// }
+    ///
+    /// let keys = (0..10).map(|i| [i]).collect();
+    /// let mut fake = Fake { keys, pos: 0, error: None };
+    ///
+    /// let report = IntegrityReport::scan(&mut fake);
+    /// assert_eq!(false, report.is_ok());
+    /// assert_eq!(8, report.keys);
+    /// assert_eq!(16, report.bytes);
+    /// assert_eq!(1, report.errors.len());
+    /// assert_eq!(Some(vec![2]), report.errors[0].after_key);
+    /// ```
+    pub fn scan<S>(source: &mut S) -> Self
+    where
+        S: ?Sized + IntegritySource,
+    {
+        let start = Instant::now();
+
+        let mut keys = 0;
+        let mut bytes = 0;
+        let mut errors = Vec::new();
+        let mut last_key: Option<Vec<u8>> = None;
+        let mut failing: Option<Error> = None;
+
+        source.seek_to_first();
+        loop {
+            match source.status() {
+                Ok(()) => failing = None,
+                Err(e) => {
+                    if failing.as_ref() != Some(&e) {
+                        errors.push(IntegrityError {
+                            after_key: last_key.clone(),
+                            error: e.clone(),
+                        });
+                        failing = Some(e);
+                    }
+                }
+            }
+
+            let (key, value) = match (source.key(), source.value()) {
+                (Some(key), Some(value)) => (key, value),
+                _ => break,
+            };
+
+            keys += 1;
+            bytes += (key.len() + value.len()) as u64;
+
+            let last = last_key.get_or_insert_with(Vec::new);
+            last.clear();
+            last.extend_from_slice(key);
+
+            source.seek_next();
+        }
+
+        IntegrityReport {
+            keys,
+            bytes,
+            elapsed: start.elapsed(),
+            errors,
+        }
+    }
+}
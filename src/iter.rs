@@ -0,0 +1,311 @@
+// Copyright 2021 Shin Yoshida
+//
+// "LGPL-3.0-or-later OR Apache-2.0 OR BSD-2-Clause"
+//
+// This is part of mouse-leveldb
+//
+//  mouse-leveldb is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU Lesser General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  mouse-leveldb is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU Lesser General Public License for more details.
+//
+//  You should have received a copy of the GNU Lesser General Public License
+//  along with mouse-leveldb.  If not, see <http://www.gnu.org/licenses/>.
+//
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted
+// provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of
+//    conditions and the following disclaimer.
+// 2. Redistributions in binary form must reproduce the above copyright notice, this
+//    list of conditions and the following disclaimer in the documentation and/or other
+//    materials provided with the distribution.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+// ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+// WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+// IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT,
+// INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+// NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+
+use crate::database::{self, Database};
+use crate::error::{self, Result};
+use crate::read_options::ReadOptions;
+use crate::snapshot::Snapshot;
+use core::marker::PhantomData;
+use core::ptr::{null, NonNull};
+use leveldb_sys::*;
+use std::os::raw::c_char;
+
+/// `Iter` is a wrapper of `*mut leveldb_iterator_t` to make sure to destroy on the drop.
+///
+/// `Iter` is a cursor on the (key, value) pairs sorted by the key. It points nothing just after
+/// created; call one of the `seek` methods before accessing the key or the value.
+pub struct Iter<'a> {
+    ptr: NonNull<leveldb_iterator_t>,
+    // Released after 'ptr' is destroyed.
+    snapshot: Option<Snapshot<'a>>,
+    _db: PhantomData<&'a Database>,
+}
+
+// 'leveldb_iterator_t' is not thread safe, however, it can be sent to another thread.
+unsafe impl Send for Iter<'_> {}
+
+impl Drop for Iter<'_> {
+    fn drop(&mut self) {
+        unsafe { leveldb_iter_destroy(self.ptr.as_ptr()) };
+    }
+}
+
+/// Creates a new instance iterating `db` with `options` .
+///
+/// # Panics
+///
+/// Causes a panic if `db` is not opened.
+pub fn new<'a>(db: &'a Database, options: &ReadOptions<'a>) -> Iter<'a> {
+    Iter {
+        ptr: create_(db, options),
+        snapshot: None,
+        _db: PhantomData,
+    }
+}
+
+/// Creates a new instance iterating a new [`Snapshot`] of `db` , and holding the snapshot.
+///
+/// The snapshot is released when the instance is dropped.
+///
+/// # Panics
+///
+/// Causes a panic if `db` is not opened.
+pub fn with_snapshot(db: &Database) -> Iter<'_> {
+    let snapshot = Snapshot::new(db);
+
+    let ptr = {
+        let mut options = ReadOptions::new();
+        options.set_snapshot(&snapshot);
+        create_(db, &options)
+    };
+
+    Iter {
+        ptr,
+        snapshot: Some(snapshot),
+        _db: PhantomData,
+    }
+}
+
+fn create_(db: &Database, options: &ReadOptions) -> NonNull<leveldb_iterator_t> {
+    unsafe {
+        let ptr = leveldb_create_iterator(database::as_ptr(db).unwrap(), options.as_ptr());
+        assert!(!ptr.is_null());
+        NonNull::new_unchecked(ptr)
+    }
+}
+
+impl<'a> Iter<'a> {
+    /// Returns the [`Snapshot`] `self` iterates if `self` holds it; otherwise returns `None` .
+    ///
+    /// `self` holds the snapshot if it was created by [`crate::snapshot_iter`] .
+    #[inline]
+    pub fn snapshot(&self) -> Option<&Snapshot<'a>> {
+        self.snapshot.as_ref()
+    }
+
+    /// Returns `true` if `self` points a (key, value) pair; otherwise, i.e. if `self` is not
+    /// positioned yet or passed the end, returns `false` .
+    #[inline]
+    pub fn is_valid(&self) -> bool {
+        unsafe { leveldb_iter_valid(self.ptr.as_ptr()) != 0 }
+    }
+
+    /// Moves `self` to the first (key, value) pair.
+    #[inline]
+    pub fn seek_to_first(&mut self) {
+        unsafe { leveldb_iter_seek_to_first(self.ptr.as_ptr()) };
+    }
+
+    /// Moves `self` to the last (key, value) pair.
+    #[inline]
+    pub fn seek_to_last(&mut self) {
+        unsafe { leveldb_iter_seek_to_last(self.ptr.as_ptr()) };
+    }
+
+    /// Moves `self` to the first (key, value) pair whose key is greater than or equals to `key` .
+    #[inline]
+    pub fn seek(&mut self, key: &[u8]) {
+        unsafe { leveldb_iter_seek(self.ptr.as_ptr(), key.as_ptr() as *const c_char, key.len()) };
+    }
+
+    /// Moves `self` to the next (key, value) pair.
+    ///
+    /// # Panics
+    ///
+    /// Causes a panic if `self` is not valid.
+    #[inline]
+    pub fn seek_next(&mut self) {
+        assert!(self.is_valid());
+        unsafe { leveldb_iter_next(self.ptr.as_ptr()) };
+    }
+
+    /// Moves `self` to the previous (key, value) pair.
+    ///
+    /// # Panics
+    ///
+    /// Causes a panic if `self` is not valid.
+    #[inline]
+    pub fn seek_prev(&mut self) {
+        assert!(self.is_valid());
+        unsafe { leveldb_iter_prev(self.ptr.as_ptr()) };
+    }
+
+    /// Returns the key `self` points if valid; otherwise returns `None` .
+    #[inline]
+    pub fn key(&self) -> Option<&[u8]> {
+        if self.is_valid() {
+            unsafe {
+                let mut len: usize = 0;
+                let ptr = leveldb_iter_key(self.ptr.as_ptr(), &mut len as *mut usize);
+                if len == 0 {
+                    Some(&[])
+                } else {
+                    Some(core::slice::from_raw_parts(ptr as *const u8, len))
+                }
+            }
+        } else {
+            None
+        }
+    }
+
+    /// Returns the value `self` points if valid; otherwise returns `None` .
+    #[inline]
+    pub fn value(&self) -> Option<&[u8]> {
+        if self.is_valid() {
+            unsafe {
+                let mut len: usize = 0;
+                let ptr = leveldb_iter_value(self.ptr.as_ptr(), &mut len as *mut usize);
+                if len == 0 {
+                    Some(&[])
+                } else {
+                    Some(core::slice::from_raw_parts(ptr as *const u8, len))
+                }
+            }
+        } else {
+            None
+        }
+    }
+
+    /// Collects the keys from the current position to the end, and moves `self` to the end.
+    ///
+    /// Returns an empty vector if `self` is not valid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::{Database, WriteBatch};
+    /// use std::ffi::CString;
+    /// use tempfile;
+    ///
+    /// let tmp = tempfile::tempdir().unwrap();
+    /// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+    ///
+    /// let mut db = Database::new();
+    /// db.open(&path).unwrap();
+    ///
+    /// let mut batch = WriteBatch::new();
+    /// batch.put(&[1], &[10]);
+    /// batch.put(&[2], &[20]);
+    /// batch.put(&[3], &[30]);
+    /// mouse_leveldb::write(&db, &mut batch).unwrap();
+    ///
+    /// let mut it = mouse_leveldb::iter(&db);
+    /// assert_eq!(true, it.collect_keys().unwrap().is_empty());
+    ///
+    /// it.seek(&[2]);
+    /// assert_eq!(vec![vec![2], vec![3]], it.collect_keys().unwrap());
+    /// assert_eq!(false, it.is_valid());
+    /// ```
+    pub fn collect_keys(&mut self) -> Result<Vec<Vec<u8>>> {
+        let mut ret = Vec::new();
+        while let Some(key) = self.key() {
+            ret.push(key.to_vec());
+            self.seek_next();
+        }
+        self.status().map(|_| ret)
+    }
+
+    /// Collects the (key, value) pairs from the current position to the end, and moves `self` to
+    /// the end.
+    ///
+    /// Returns an empty vector if `self` is not valid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::{Database, WriteBatch};
+    /// use std::ffi::CString;
+    /// use tempfile;
+    ///
+    /// let tmp = tempfile::tempdir().unwrap();
+    /// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+    ///
+    /// let mut db = Database::new();
+    /// db.open(&path).unwrap();
+    ///
+    /// let mut batch = WriteBatch::new();
+    /// batch.put(&[1], &[10]);
+    /// batch.put(&[2], &[20]);
+    /// mouse_leveldb::write(&db, &mut batch).unwrap();
+    ///
+    /// let mut it = mouse_leveldb::iter(&db);
+    /// it.seek_to_first();
+    /// assert_eq!(
+    ///     vec![(vec![1], vec![10]), (vec![2], vec![20])],
+    ///     it.collect_entries().unwrap()
+    /// );
+    /// ```
+    #[allow(clippy::type_complexity)]
+    pub fn collect_entries(&mut self) -> Result<Vec<(Vec<u8>, Vec<u8>)>> {
+        let mut ret = Vec::new();
+        while let (Some(key), Some(value)) = (self.key(), self.value()) {
+            ret.push((key.to_vec(), value.to_vec()));
+            self.seek_next();
+        }
+        self.status().map(|_| ret)
+    }
+
+    /// Returns the error if `self` has encountered any; otherwise returns `Ok` .
+    ///
+    /// `self` becomes invalid after encountering an error.
+    pub fn status(&self) -> Result<()> {
+        let mut error: *const c_char = null();
+        unsafe {
+            leveldb_iter_get_error(self.ptr.as_ptr(), &mut error as *mut *const c_char);
+            match NonNull::new(error as *mut c_char) {
+                None => Ok(()),
+                Some(ptr) => Err(error::new(ptr)),
+            }
+        }
+    }
+}
@@ -52,40 +52,226 @@
 // POSSIBILITY OF SUCH DAMAGE.
 
 //! `mouse-leveldb` is a wrapper of crate `leveldb-sys` for `mouse` .
+//!
+//! # Binary keys and values
+//!
+//! Keys and values are arbitrary bytes. They are passed to LevelDB with the length, so they may
+//! contain NUL bytes; nothing is truncated at NUL. (Only the path to the database is a `CStr` .)
+//!
+//! ```
+//! use mouse_leveldb::{Database, WriteBatch};
+//! use std::ffi::CString;
+//! use tempfile;
+//!
+//! let tmp = tempfile::tempdir().unwrap();
+//! let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+//!
+//! let mut db = Database::new();
+//! db.open(&path).unwrap();
+//!
+//! let all: Vec<u8> = (0..=255).collect();
+//! let keys: Vec<Vec<u8>> = vec![vec![0], vec![0, 0], vec![1, 0, 2], all.clone()];
+//! let value: Vec<u8> = all.iter().rev().copied().collect();
+//!
+//! // Put
+//! let mut batch = WriteBatch::new();
+//! for key in keys.iter() {
+//!     batch.put(key, &value);
+//! }
+//! mouse_leveldb::write(&db, &mut batch).unwrap();
+//!
+//! // Get
+//! for key in keys.iter() {
+//!     assert_eq!(value.as_slice(), mouse_leveldb::get(&db, key).unwrap().as_ref());
+//! }
+//!
+//! // Iteration
+//! let mut sorted = keys.clone();
+//! sorted.sort();
+//! let mut it = mouse_leveldb::iter(&db);
+//! it.seek_to_first();
+//! let entries = it.collect_entries().unwrap();
+//! assert_eq!(sorted, entries.iter().map(|(k, _)| k.clone()).collect::<Vec<_>>());
+//! assert!(entries.iter().all(|(_, v)| v == &value));
+//!
+//! // Delete only [0, 0] , which differs from [0] only after NUL.
+//! let mut batch = WriteBatch::new();
+//! batch.delete(&[0, 0]);
+//! mouse_leveldb::write(&db, &mut batch).unwrap();
+//!
+//! assert_eq!(0, mouse_leveldb::get(&db, &[0, 0]).unwrap().len());
+//! assert_eq!(value.as_slice(), mouse_leveldb::get(&db, &[0]).unwrap().as_ref());
+//! assert_eq!(3, mouse_leveldb::count(&db).unwrap());
+//! ```
+//!
+//! # Thread safety
+//!
+//! [`Database`] is `Send + Sync` . The functions taking `&Database` , i.e. reading, writing,
+//! iterating, and creating [`Snapshot`] , can be called from any number of threads at the same
+//! time. LevelDB serializes the writes internally. The methods to open or to close the database
+//! take `&mut Database` , so they never race with the others.
+//!
+//! [`Iter`] is `Send` but not `Sync` ; each thread should create its own iterator. An iterator
+//! sees the state when it was created, so the writes by the other threads while iterating are
+//! invisible from it.
+//!
+//! [`Snapshot`] , [`ReadOptions`] , [`WriteOptions`] , [`WriteBatch`] , and [`Octets`] are
+//! `Send + Sync` . The default options this crate uses internally are created once and never
+//! modified.
+//!
+//! ```
+//! use mouse_leveldb::{Database, WriteBatch};
+//! use std::ffi::CString;
+//! use std::thread;
+//! use tempfile;
+//!
+//! const THREADS: u8 = 8;
+//! const KEYS: u8 = 64;
+//!
+//! let tmp = tempfile::tempdir().unwrap();
+//! let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+//!
+//! let mut db = Database::new();
+//! db.open(&path).unwrap();
+//!
+//! let mut batch = WriteBatch::new();
+//! for k in 0..KEYS {
+//!     batch.put(&[0, k], &[k]);
+//! }
+//! mouse_leveldb::write(&db, &mut batch).unwrap();
+//!
+//! let db = &db;
+//! thread::scope(|scope| {
+//!     for t in 0..THREADS {
+//!         scope.spawn(move || {
+//!             for round in 0..32 {
+//!                 // Each thread writes its own keys while the others are reading.
+//!                 let mut batch = WriteBatch::new();
+//!                 batch.put(&[1, t], &[round]);
+//!                 mouse_leveldb::write(db, &mut batch).unwrap();
+//!
+//!                 for k in 0..KEYS {
+//!                     assert_eq!(&[k], mouse_leveldb::get(db, &[0, k]).unwrap().as_ref());
+//!                 }
+//!
+//!                 let mut it = mouse_leveldb::iter(db);
+//!                 it.seek(&[0]);
+//!                 let mut n = 0;
+//!                 while let Some(key) = it.key() {
+//!                     if key[0] != 0 {
+//!                         break;
+//!                     }
+//!                     assert_eq!(Some(&[key[1]] as &[u8]), it.value());
+//!                     n += 1;
+//!                     it.seek_next();
+//!                 }
+//!                 it.status().unwrap();
+//!                 assert_eq!(KEYS, n);
+//!
+//!                 let value = mouse_leveldb::get(db, &[1, t]).unwrap();
+//!                 assert_eq!(&[round], value.as_ref());
+//!             }
+//!         });
+//!     }
+//! });
+//!
+//! assert_eq!(KEYS as u64 + THREADS as u64, mouse_leveldb::count(db).unwrap());
+//! ```
 
 #![deny(missing_docs)]
 
+#[cfg(feature = "async")]
+mod async_io;
+#[cfg(feature = "testing")]
+mod benchmark_db;
+mod cache;
+mod cursor;
 mod database;
+mod database_builder;
+mod database_set;
+mod disk_usage;
+mod env;
 mod error;
+#[cfg(feature = "testing")]
+mod fault;
+mod filter_policy;
+mod integrity;
+mod iter;
+mod namespace;
 mod octets;
+mod octets_display;
+mod octets_reader;
 mod options;
+mod read_only;
 mod read_options;
+mod scoped_db;
+mod shared_octets;
+mod sized_iter;
+mod snapshot;
+#[cfg(feature = "testing")]
+mod test_db;
+mod transaction;
+#[cfg(feature = "serde")]
+mod typed;
+mod version;
 mod write_batch;
+mod write_batch_writer;
 mod write_options;
 
+#[cfg(feature = "async")]
+pub use async_io::{get_async, write_async};
+#[cfg(feature = "testing")]
+pub use benchmark_db::BenchmarkDb;
+pub use cache::Cache;
 use core::ptr::{null_mut, NonNull};
-use core::result::Result;
-pub use database::Database;
-pub use error::Error;
+pub use cursor::Cursor;
+pub use database::{Database, Opened, RetryPolicy};
+pub use database_builder::DatabaseBuilder;
+pub use database_set::DatabaseSet;
+pub use disk_usage::{DiskUsage, FileStats};
+pub use env::Env;
+pub use error::{Error, ErrorKind, Result};
+#[cfg(feature = "testing")]
+pub use fault::{inject_fault, Fault, FaultGuard};
+pub use integrity::{IntegrityError, IntegrityReport, IntegritySource};
+pub use iter::Iter;
 use leveldb_sys::*;
+pub use namespace::Namespace;
+#[cfg(feature = "testing")]
+pub use octets::live_octets;
 pub use octets::Octets;
+pub use octets_display::OctetsDisplay;
+pub use octets_reader::OctetsReader;
 use once_cell::sync::Lazy;
-use options::Options;
-use read_options::ReadOptions;
+pub use options::Options;
+pub use read_only::ReadOnly;
+pub use read_options::ReadOptions;
+pub use scoped_db::ScopedDb;
+pub use shared_octets::SharedOctets;
+pub use sized_iter::SizedIter;
+pub use snapshot::Snapshot;
 use std::os::raw::c_char;
-pub use write_batch::WriteBatch;
-use write_options::WriteOptions;
+#[cfg(feature = "testing")]
+pub use test_db::TestDb;
+pub use transaction::Transaction;
+#[cfg(feature = "serde")]
+pub use typed::{Bincode, Codec, TypedDatabase};
+pub use version::{
+    leveldb_version, version, Version, LEVELDB_MAJOR_VERSION, LEVELDB_MINOR_VERSION,
+};
+pub use write_batch::{batch_entries, BatchEntry, WriteBatch};
+pub use write_batch_writer::WriteBatchWriter;
+pub use write_options::WriteOptions;
 
 static OPTIONS: Lazy<Options> = Lazy::new(|| Options::new());
 static READ_OPTIONS: Lazy<ReadOptions> = Lazy::new(|| ReadOptions::new());
 static WRITE_OPTIONS: Lazy<WriteOptions> = Lazy::new(|| WriteOptions::new());
 
 /// Flushes `batch` to `db` .
-/// After this method is called, `batch` will be cleared even if failed.
+/// After this method succeeds, `batch` will be cleared; if failed, `batch` is left as it is so
+/// that the caller can retry it.
 ///
-/// # Panics
-///
-/// Causes a panic if `db` is not opened.
+/// It is an error if `db` is not opened.
 ///
 /// # Examples
 ///
@@ -113,29 +299,172 @@ static WRITE_OPTIONS: Lazy<WriteOptions> = Lazy::new(|| WriteOptions::new());
 /// batch.put(key2, value2);
 /// batch.put(key1, value3);
 ///
-/// mouse_leveldb::write(&db, &mut batch);
+/// mouse_leveldb::write(&db, &mut batch).unwrap();
+/// assert_eq!(0, batch.len());
+///
+/// assert_eq!(value3, mouse_leveldb::get(&db, key1).unwrap().as_ref());
+/// assert_eq!(value2, mouse_leveldb::get(&db, key2).unwrap().as_ref());
+/// ```
+///
+/// `write` and [`get`] return an error instead of panicking if `db` is not opened.
+///
+/// ```
+/// use mouse_leveldb::{Database, ErrorKind, WriteBatch};
+/// use std::ffi::CString;
+/// use tempfile;
+///
+/// let tmp = tempfile::tempdir().unwrap();
+/// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+///
+/// // Never opened.
+/// let mut db = Database::new();
+/// let mut batch = WriteBatch::new();
+/// batch.put(&[1], &[10]);
+/// assert_eq!(ErrorKind::NotOpened, mouse_leveldb::write(&db, &mut batch).unwrap_err().kind());
+/// assert_eq!(ErrorKind::NotOpened, mouse_leveldb::get(&db, &[1]).unwrap_err().kind());
+///
+/// db.open(&path).unwrap();
+/// batch.put(&[1], &[10]);
+/// mouse_leveldb::write(&db, &mut batch).unwrap();
+///
+/// // Closed.
+/// db.close();
+/// batch.put(&[2], &[20]);
+/// assert_eq!(ErrorKind::NotOpened, mouse_leveldb::write(&db, &mut batch).unwrap_err().kind());
+/// assert_eq!(ErrorKind::NotOpened, mouse_leveldb::get(&db, &[1]).unwrap_err().kind());
+///
+/// // The failed batch is kept.
+/// assert_eq!(1, batch.len());
+///
+/// // The data is still readable after reopening.
+/// db.reopen().unwrap();
+/// assert_eq!(&[10], mouse_leveldb::get(&db, &[1]).unwrap().as_ref());
+/// assert_eq!(0, mouse_leveldb::get(&db, &[2]).unwrap().len());
+///
+/// // Retry the batch.
+/// mouse_leveldb::write(&db, &mut batch).unwrap();
+/// assert_eq!(0, batch.len());
+/// assert_eq!(&[20], mouse_leveldb::get(&db, &[2]).unwrap().as_ref());
+/// ```
+#[must_use = "write errors must not be ignored"]
+pub fn write(db: &Database, batch: &mut WriteBatch) -> Result<()> {
+    write_with_options(db, batch, &WRITE_OPTIONS)
+}
+
+/// Flushes `batch` to `db` with `options` instead of the default [`WriteOptions`] .
+/// After this method succeeds, `batch` will be cleared; if failed, `batch` is left as it is so
+/// that the caller can retry it.
+///
+/// It is an error if `db` is not opened.
+///
+/// # Examples
+///
+/// ```
+/// use mouse_leveldb::{Database, WriteBatch, WriteOptions};
+/// use std::ffi::CString;
+/// use tempfile;
+///
+/// let tmp = tempfile::tempdir().unwrap();
+/// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+///
+/// let mut db = Database::new();
+/// db.open(&path).unwrap();
+///
+/// let mut options = WriteOptions::new();
+/// options.set_sync(false);
+///
+/// let key: &[u8] = &[1, 2, 3];
+/// let value: &[u8] = &[4, 5];
+///
+/// let mut batch = WriteBatch::new();
+/// batch.put(key, value);
+///
+/// mouse_leveldb::write_with_options(&db, &mut batch, &options).unwrap();
+/// assert_eq!(0, batch.len());
+///
+/// let octets = mouse_leveldb::get(&db, key).unwrap();
+/// assert_eq!(value, octets.as_ref());
 /// ```
-pub fn write(db: &Database, batch: &mut WriteBatch) -> Result<(), Error> {
+#[must_use = "write errors must not be ignored"]
+pub fn write_with_options(
+    db: &Database,
+    batch: &mut WriteBatch,
+    options: &WriteOptions,
+) -> Result<()> {
+    write_(db, batch, options)?;
+    batch.clear();
+    Ok(())
+}
+
+/// Flushes `batch` to `db` as [`write`] does, however, does not clear `batch` .
+///
+/// It is useful to write the same `batch` to multiple databases, for example, a primary and a
+/// replica.
+///
+/// It is an error if `db` is not opened.
+///
+/// # Examples
+///
+/// ```
+/// use mouse_leveldb::{Database, WriteBatch};
+/// use std::ffi::CString;
+/// use tempfile;
+///
+/// let tmp1 = tempfile::tempdir().unwrap();
+/// let path1 = CString::new(tmp1.path().to_str().unwrap()).unwrap();
+/// let mut primary = Database::new();
+/// primary.open(&path1).unwrap();
+///
+/// let tmp2 = tempfile::tempdir().unwrap();
+/// let path2 = CString::new(tmp2.path().to_str().unwrap()).unwrap();
+/// let mut replica = Database::new();
+/// replica.open(&path2).unwrap();
+///
+/// let mut batch = WriteBatch::new();
+/// batch.put(&[1], &[10]);
+/// batch.put(&[2], &[20]);
+///
+/// let batch = batch;
+/// mouse_leveldb::write_keep(&primary, &batch).unwrap();
+/// mouse_leveldb::write_keep(&replica, &batch).unwrap();
+/// assert_eq!(2, batch.len());
+///
+/// for db in [&primary, &replica].iter() {
+///     assert_eq!(&[10], mouse_leveldb::get(db, &[1]).unwrap().as_ref());
+///     assert_eq!(&[20], mouse_leveldb::get(db, &[2]).unwrap().as_ref());
+/// }
+/// ```
+#[must_use = "write errors must not be ignored"]
+pub fn write_keep(db: &Database, batch: &WriteBatch) -> Result<()> {
+    write_(db, batch, &WRITE_OPTIONS)
+}
+
+/// Flushes `batch` to `db` with `options` without clearing `batch` .
+fn write_(db: &Database, batch: &WriteBatch, options: &WriteOptions) -> Result<()> {
+    let dbptr = database::as_ptr(db).ok_or_else(error::not_opened)?;
+
+    if db.is_read_only() {
+        return Err(error::read_only());
+    }
+
     if batch.len() == 0 {
-        Ok(())
-    } else {
-        let batch = write_batch::as_ptr(batch).unwrap();
-        let mut error: *mut c_char = null_mut();
-        let errptr: *mut *mut c_char = &mut error;
-
-        unsafe {
-            leveldb_write(
-                database::as_ptr(db).unwrap(),
-                WRITE_OPTIONS.as_ptr(),
-                batch,
-                errptr,
-            );
-            leveldb_writebatch_clear(batch);
-        }
+        return Ok(());
+    }
 
-        match NonNull::new(error) {
-            None => Ok(()),
-            Some(ptr) => unsafe { Err(error::new(ptr)) },
+    let mut error: *mut c_char = null_mut();
+    let errptr: *mut *mut c_char = &mut error;
+
+    unsafe {
+        let batchptr = write_batch::as_ptr(batch).unwrap();
+        leveldb_write(dbptr, options.as_ptr(), batchptr, errptr);
+    }
+
+    match NonNull::new(error) {
+        None => Ok(()),
+        Some(ptr) => {
+            let e = unsafe { error::new(ptr) };
+            let path = db.path().unwrap_or_default();
+            Err(error::with_operation("write", path, None, e))
         }
     }
 }
@@ -145,9 +474,7 @@ pub fn write(db: &Database, batch: &mut WriteBatch) -> Result<(), Error> {
 /// If no such `key` is stored, returns an empty [`Octets`] .
 /// (It is not an error because the query itself is succeeded.)
 ///
-/// # Panics
-///
-/// Causes a panic if `db` is not opened.
+/// It is an error if `db` is not opened.
 ///
 /// # Examples
 ///
@@ -174,7 +501,7 @@ pub fn write(db: &Database, batch: &mut WriteBatch) -> Result<(), Error> {
 /// let mut batch = WriteBatch::new();
 ///
 /// batch.put(key, value);
-/// mouse_leveldb::write(&db, &mut batch);
+/// mouse_leveldb::write(&db, &mut batch).unwrap();
 ///
 /// // Found the value after insert.
 /// {
@@ -182,8 +509,362 @@ pub fn write(db: &Database, batch: &mut WriteBatch) -> Result<(), Error> {
 ///     assert_eq!(value, octets.unwrap().as_ref());
 /// }
 /// ```
+///
+/// The error tells the path to the database and the key.
+///
+/// ```
+/// use mouse_leveldb::{Database, ErrorKind, WriteBatch};
+/// use std::ffi::CString;
+/// use std::fs;
+/// use tempfile;
+///
+/// let tmp = tempfile::tempdir().unwrap();
+/// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+///
+/// let mut db = Database::new();
+/// db.open(&path).unwrap();
+///
+/// let key: &[u8] = &[0xde, 0xad, 0xbe, 0xef];
+/// let mut batch = WriteBatch::new();
+/// batch.put(key, &[1]);
+/// mouse_leveldb::write(&db, &mut batch).unwrap();
+///
+/// // Reopening writes the data into the table file.
+/// db.reopen().unwrap();
+/// db.close();
+///
+/// // Breaks the table file.
+/// for entry in fs::read_dir(tmp.path()).unwrap() {
+///     let file = entry.unwrap().path();
+///     if file.extension().map(|ext| ext == "ldb").unwrap_or(false) {
+///         let len = fs::metadata(&file).unwrap().len() as usize;
+///         fs::write(&file, vec![0; len]).unwrap();
+///     }
+/// }
+///
+/// db.open(&path).unwrap();
+/// let e = mouse_leveldb::get(&db, key).unwrap_err();
+/// assert_eq!(ErrorKind::Corruption, e.kind());
+///
+/// let message = e.leveldb_message().unwrap();
+/// let context = format!("get on {} (key=0xdeadbeef)", path.to_str().unwrap());
+/// assert_eq!(format!("{}: {}", context, message), e.to_string());
+/// ```
+#[must_use = "read errors must not be ignored"]
 #[inline]
-pub fn get(db: &Database, key: &[u8]) -> Result<Octets, Error> {
+pub fn get(db: &Database, key: &[u8]) -> Result<Octets> {
+    match get_with_options(db, key, &READ_OPTIONS)? {
+        Some(octets) => Ok(octets),
+        None => Ok(unsafe { octets::new(null_mut(), 0) }),
+    }
+}
+
+/// Looks up each of `keys` and returns the results in the same order as `keys` .
+///
+/// Each result is `None` if the key is not stored.
+///
+/// It is an error if `db` is not opened.
+///
+/// # Examples
+///
+/// ```
+/// use mouse_leveldb::{Database, WriteBatch};
+/// use std::ffi::CString;
+/// use tempfile;
+///
+/// let tmp = tempfile::tempdir().unwrap();
+/// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+///
+/// let mut db = Database::new();
+/// db.open(&path).unwrap();
+///
+/// let mut batch = WriteBatch::new();
+/// batch.put(&[1], &[10]);
+/// batch.put(&[3], &[30]);
+/// mouse_leveldb::write(&db, &mut batch).unwrap();
+///
+/// let results = mouse_leveldb::get_many(&db, &[&[3], &[2], &[1]]);
+/// assert_eq!(3, results.len());
+/// assert_eq!(Some(&[30_u8] as &[u8]), results[0].as_ref().unwrap().as_deref());
+/// assert_eq!(None, results[1].as_ref().unwrap().as_deref());
+/// assert_eq!(Some(&[10_u8] as &[u8]), results[2].as_ref().unwrap().as_deref());
+/// ```
+#[must_use = "read errors must not be ignored"]
+pub fn get_many(db: &Database, keys: &[&[u8]]) -> Vec<Result<Option<Octets>>> {
+    keys.iter()
+        .map(|key| get_with_options(db, key, &READ_OPTIONS))
+        .collect()
+}
+
+/// Creates a new [`Iter`] to iterate all the (key, value) pairs stored in `db` .
+///
+/// # Panics
+///
+/// Causes a panic if `db` is not opened.
+///
+/// # Examples
+///
+/// ```
+/// use mouse_leveldb::{Database, WriteBatch};
+/// use std::ffi::CString;
+/// use tempfile;
+///
+/// let tmp = tempfile::tempdir().unwrap();
+/// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+///
+/// let mut db = Database::new();
+/// db.open(&path).unwrap();
+///
+/// let mut batch = WriteBatch::new();
+/// batch.put(&[3], &[30]);
+/// batch.put(&[1], &[10]);
+/// batch.put(&[2], &[20]);
+/// mouse_leveldb::write(&db, &mut batch).unwrap();
+///
+/// let mut it = mouse_leveldb::iter(&db);
+/// assert_eq!(false, it.is_valid());
+///
+/// it.seek_to_first();
+/// assert_eq!(Some(&[1_u8] as &[u8]), it.key());
+/// assert_eq!(Some(&[10_u8] as &[u8]), it.value());
+///
+/// it.seek_next();
+/// assert_eq!(Some(&[2_u8] as &[u8]), it.key());
+///
+/// it.seek_to_last();
+/// assert_eq!(Some(&[3_u8] as &[u8]), it.key());
+///
+/// it.seek_prev();
+/// assert_eq!(Some(&[2_u8] as &[u8]), it.key());
+///
+/// it.seek(&[2, 0]);
+/// assert_eq!(Some(&[3_u8] as &[u8]), it.key());
+///
+/// it.seek_next();
+/// assert_eq!(false, it.is_valid());
+/// assert_eq!(None, it.key());
+/// assert!(it.status().is_ok());
+/// ```
+pub fn iter(db: &Database) -> Iter<'_> {
+    iter::new(db, &READ_OPTIONS)
+}
+
+/// Creates a new [`Iter`] to iterate all the (key, value) pairs stored in `db` when this function
+/// is called.
+///
+/// Unlike [`iter`] , the writes after this function is called are invisible from the returned
+/// iterator. The iterator holds a new [`Snapshot`] and releases it on the drop; see
+/// [`Iter::snapshot`] to read other keys from the same snapshot.
+///
+/// # Panics
+///
+/// Causes a panic if `db` is not opened.
+///
+/// # Examples
+///
+/// ```
+/// use mouse_leveldb::{Database, WriteBatch};
+/// use std::ffi::CString;
+/// use tempfile;
+///
+/// let tmp = tempfile::tempdir().unwrap();
+/// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+///
+/// let mut db = Database::new();
+/// db.open(&path).unwrap();
+///
+/// let mut batch = WriteBatch::new();
+/// batch.put(&[1], &[10]);
+/// batch.put(&[2], &[20]);
+/// mouse_leveldb::write(&db, &mut batch).unwrap();
+///
+/// let mut it = mouse_leveldb::snapshot_iter(&db);
+///
+/// batch.put(&[1], &[11]);
+/// batch.put(&[3], &[30]);
+/// batch.delete(&[2]);
+/// mouse_leveldb::write(&db, &mut batch).unwrap();
+///
+/// it.seek_to_first();
+/// assert_eq!(
+///     vec![(vec![1], vec![10]), (vec![2], vec![20])],
+///     it.collect_entries().unwrap()
+/// );
+///
+/// let snapshot = it.snapshot().unwrap();
+/// let value = mouse_leveldb::get_with_snapshot(&db, &[1], snapshot, true).unwrap();
+/// assert_eq!(Some(&[10_u8] as &[u8]), value.as_deref());
+///
+/// assert_eq!(true, mouse_leveldb::iter(&db).snapshot().is_none());
+/// ```
+pub fn snapshot_iter(db: &Database) -> Iter<'_> {
+    iter::with_snapshot(db)
+}
+
+/// Counts the keys stored in `db` .
+///
+/// LevelDB does not know how many keys are stored; this function scans all the keys, so it takes
+/// O(N) time. It is recommended to cache the result if `db` is large.
+///
+/// It is an error if `db` is not opened.
+///
+/// # Examples
+///
+/// ```
+/// use mouse_leveldb::{Database, ErrorKind, WriteBatch};
+/// use std::ffi::CString;
+/// use tempfile;
+///
+/// let tmp = tempfile::tempdir().unwrap();
+/// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+///
+/// let mut db = Database::new();
+/// assert_eq!(ErrorKind::NotOpened, mouse_leveldb::count(&db).unwrap_err().kind());
+///
+/// db.open(&path).unwrap();
+/// assert_eq!(0, mouse_leveldb::count(&db).unwrap());
+///
+/// let mut batch = WriteBatch::new();
+/// batch.put(&[1], &[10]);
+/// batch.put(&[2], &[20]);
+/// batch.put(&[1], &[11]);
+/// mouse_leveldb::write(&db, &mut batch).unwrap();
+///
+/// assert_eq!(2, mouse_leveldb::count(&db).unwrap());
+/// ```
+#[must_use = "read errors must not be ignored"]
+pub fn count(db: &Database) -> Result<u64> {
+    if database::as_ptr(db).is_none() {
+        return Err(error::not_opened());
+    }
+
+    let mut it = iter(db);
+    it.seek_to_first();
+
+    let mut n: u64 = 0;
+    while it.is_valid() {
+        n += 1;
+        it.seek_next();
+    }
+
+    it.status()?;
+    Ok(n)
+}
+
+/// Deletes all the keys in the range `[start, end)` and returns how many keys were deleted.
+///
+/// `end` is exclusive, however, the empty `end` means no upper bound; i.e.
+/// `delete_range(db, &[], &[])` deletes all the keys in `db` . Nothing is deleted if `end` is not
+/// empty and `end <= start` .
+///
+/// LevelDB has no native range delete. This function iterates the range and writes the deletes at
+/// once.
+///
+/// It is an error if `db` is not opened.
+///
+/// # Warnings
+///
+/// Iterating and writing are not done atomically. The keys written into the range by another
+/// thread or process while iterating may survive.
+///
+/// # Examples
+///
+/// ```
+/// use mouse_leveldb::{Database, ErrorKind, WriteBatch};
+/// use std::ffi::CString;
+/// use tempfile;
+///
+/// let tmp = tempfile::tempdir().unwrap();
+/// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+///
+/// let mut db = Database::new();
+/// let e = mouse_leveldb::delete_range(&db, &[], &[]).unwrap_err();
+/// assert_eq!(ErrorKind::NotOpened, e.kind());
+///
+/// db.open(&path).unwrap();
+///
+/// let mut batch = WriteBatch::new();
+/// for i in 0..10_u8 {
+///     batch.put(&[i], &[i]);
+/// }
+/// mouse_leveldb::write(&db, &mut batch).unwrap();
+///
+/// // Deletes [3], [4], and [5] .
+/// assert_eq!(3, mouse_leveldb::delete_range(&db, &[3], &[6]).unwrap());
+/// assert_eq!(&[2], mouse_leveldb::get(&db, &[2]).unwrap().as_ref());
+/// assert_eq!(0, mouse_leveldb::get(&db, &[3]).unwrap().len());
+/// assert_eq!(0, mouse_leveldb::get(&db, &[5]).unwrap().len());
+/// assert_eq!(&[6], mouse_leveldb::get(&db, &[6]).unwrap().as_ref());
+///
+/// // The empty range.
+/// assert_eq!(0, mouse_leveldb::delete_range(&db, &[8], &[8]).unwrap());
+/// assert_eq!(0, mouse_leveldb::delete_range(&db, &[9], &[1]).unwrap());
+/// assert_eq!(7, mouse_leveldb::count(&db).unwrap());
+///
+/// // The whole database.
+/// assert_eq!(7, mouse_leveldb::delete_range(&db, &[], &[]).unwrap());
+/// assert_eq!(0, mouse_leveldb::count(&db).unwrap());
+/// ```
+#[must_use = "write errors must not be ignored"]
+pub fn delete_range(db: &Database, start: &[u8], end: &[u8]) -> Result<u64> {
+    let mut batch = WriteBatch::new();
+    let n = batch.delete_range(db, start, end)? as u64;
+    write(db, &mut batch)?;
+    Ok(n)
+}
+
+/// Tries to fetch the value corresponding to `key` with `options` .
+///
+/// Returns `None` if no such `key` is stored.
+///
+/// Unlike [`get`] , which uses the default [`ReadOptions`] , `options` can read from a
+/// [`Snapshot`] or disable filling the block cache.
+///
+/// It is an error if `db` is not opened.
+///
+/// # Examples
+///
+/// ```
+/// use mouse_leveldb::{Database, ReadOptions, Snapshot, WriteBatch};
+/// use std::ffi::CString;
+/// use tempfile;
+///
+/// let tmp = tempfile::tempdir().unwrap();
+/// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+///
+/// let mut db = Database::new();
+/// db.open(&path).unwrap();
+///
+/// let key: &[u8] = &[1];
+/// let mut batch = WriteBatch::new();
+/// batch.put(key, &[10]);
+/// mouse_leveldb::write(&db, &mut batch).unwrap();
+///
+/// let snapshot = Snapshot::new(&db);
+/// let mut options = ReadOptions::new();
+/// options.set_snapshot(&snapshot);
+/// options.set_fill_cache(false);
+///
+/// batch.put(key, &[20]);
+/// mouse_leveldb::write(&db, &mut batch).unwrap();
+///
+/// // Reads the value when 'snapshot' was created.
+/// let octets = mouse_leveldb::get_with_options(&db, key, &options).unwrap().unwrap();
+/// assert_eq!(&[10], octets.as_ref());
+///
+/// let octets = mouse_leveldb::get(&db, key).unwrap();
+/// assert_eq!(&[20], octets.as_ref());
+///
+/// assert_eq!(true, mouse_leveldb::get_with_options(&db, &[2], &options).unwrap().is_none());
+/// ```
+#[must_use = "read errors must not be ignored"]
+pub fn get_with_options(
+    db: &Database,
+    key: &[u8],
+    options: &ReadOptions,
+) -> Result<Option<Octets>> {
+    let dbptr = database::as_ptr(db).ok_or_else(error::not_opened)?;
+
     let mut error: *mut c_char = null_mut();
     let errptr: *mut *mut c_char = &mut error;
 
@@ -191,8 +872,8 @@ pub fn get(db: &Database, key: &[u8]) -> Result<Octets, Error> {
 
     unsafe {
         let pval = leveldb_get(
-            database::as_ptr(db).unwrap(),
-            READ_OPTIONS.as_ptr(),
+            dbptr,
+            options.as_ptr(),
             key.as_ptr() as *const c_char,
             key.len(),
             &mut vallen as *mut usize,
@@ -200,8 +881,357 @@ pub fn get(db: &Database, key: &[u8]) -> Result<Octets, Error> {
         );
 
         match NonNull::new(error) {
-            Some(ptr) => Err(error::new(ptr)),
-            None => Ok(octets::new(pval as *mut u8, vallen)),
+            Some(ptr) => {
+                let path = db.path().unwrap_or_default();
+                Err(error::with_operation(
+                    "get",
+                    path,
+                    Some(key),
+                    error::new(ptr),
+                ))
+            }
+            None if pval.is_null() => Ok(None),
+            None => Ok(Some(octets::new(pval as *mut u8, vallen))),
         }
     }
 }
+
+/// Looks up `key` in `snapshot` and returns the value.
+///
+/// Returns `None` if no such `key` is stored when `snapshot` was created.
+///
+/// This is a shorthand of [`get_with_options`] with [`ReadOptions`] holding `snapshot` and
+/// `fill_cache` . Pass `false` as `fill_cache` for the bulk reads not to evict the hot blocks from
+/// the block cache.
+///
+/// It is an error if `db` is not opened.
+///
+/// # Examples
+///
+/// ```
+/// use mouse_leveldb::{Database, Snapshot, WriteBatch};
+/// use std::ffi::CString;
+/// use tempfile;
+///
+/// let tmp = tempfile::tempdir().unwrap();
+/// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+///
+/// let mut db = Database::new();
+/// db.open(&path).unwrap();
+///
+/// let mut batch = WriteBatch::new();
+/// batch.put(&[1], &[10]);
+/// mouse_leveldb::write(&db, &mut batch).unwrap();
+///
+/// let snapshot = Snapshot::new(&db);
+///
+/// batch.put(&[1], &[11]);
+/// batch.put(&[2], &[20]);
+/// mouse_leveldb::write(&db, &mut batch).unwrap();
+///
+/// let octets = mouse_leveldb::get_with_snapshot(&db, &[1], &snapshot, false).unwrap();
+/// assert_eq!(Some(&[10_u8] as &[u8]), octets.as_deref());
+///
+/// let octets = mouse_leveldb::get_with_snapshot(&db, &[2], &snapshot, true).unwrap();
+/// assert_eq!(true, octets.is_none());
+/// ```
+#[must_use = "read errors must not be ignored"]
+pub fn get_with_snapshot(
+    db: &Database,
+    key: &[u8],
+    snapshot: &Snapshot,
+    fill_cache: bool,
+) -> Result<Option<Octets>> {
+    let mut options = ReadOptions::new();
+    options.set_snapshot(snapshot);
+    options.set_fill_cache(fill_cache);
+    get_with_options(db, key, &options)
+}
+
+/// Stores `new` as the value corresponding to `key` if the current value is `expected` ; otherwise
+/// does nothing.
+///
+/// `expected` is `None` if `key` is expected not to be stored.
+/// Returns `true` if `new` is stored, or `false` if the current value is not `expected` .
+///
+/// It is an error if `db` is not opened.
+///
+/// # Warnings
+///
+/// This function reads the current value from a snapshot and then writes `new` ; they are not
+/// done atomically. If another thread or process writes `key` between them, the update will be
+/// lost. This function is safe only when the caller serializes the writes to `key` .
+///
+/// # Examples
+///
+/// ```
+/// use mouse_leveldb::{Database, ErrorKind};
+/// use std::ffi::CString;
+/// use tempfile;
+///
+/// let tmp = tempfile::tempdir().unwrap();
+/// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+///
+/// let mut db = Database::new();
+/// let e = mouse_leveldb::compare_and_swap(&db, &[1], None, &[]).unwrap_err();
+/// assert_eq!(ErrorKind::NotOpened, e.kind());
+///
+/// db.open(&path).unwrap();
+///
+/// let key: &[u8] = &[1, 2, 3];
+/// let value1: &[u8] = &[4];
+/// let value2: &[u8] = &[5, 6];
+///
+/// // Stores 'value1' because 'key' is not stored yet.
+/// assert_eq!(true, mouse_leveldb::compare_and_swap(&db, key, None, value1).unwrap());
+/// assert_eq!(value1, mouse_leveldb::get(&db, key).unwrap().as_ref());
+///
+/// // Does nothing because the current value is not 'value2'.
+/// assert_eq!(false, mouse_leveldb::compare_and_swap(&db, key, Some(value2), value2).unwrap());
+/// assert_eq!(false, mouse_leveldb::compare_and_swap(&db, key, None, value2).unwrap());
+/// assert_eq!(value1, mouse_leveldb::get(&db, key).unwrap().as_ref());
+///
+/// // Stores 'value2' because the current value is 'value1'.
+/// assert_eq!(true, mouse_leveldb::compare_and_swap(&db, key, Some(value1), value2).unwrap());
+/// assert_eq!(value2, mouse_leveldb::get(&db, key).unwrap().as_ref());
+/// ```
+#[must_use = "write errors must not be ignored"]
+pub fn compare_and_swap(
+    db: &Database,
+    key: &[u8],
+    expected: Option<&[u8]>,
+    new: &[u8],
+) -> Result<bool> {
+    if database::as_ptr(db).is_none() {
+        return Err(error::not_opened());
+    }
+
+    let current = {
+        let snapshot = Snapshot::new(db);
+        let mut options = ReadOptions::new();
+        options.set_snapshot(&snapshot);
+        get_with_options(db, key, &options)?
+    };
+
+    if current.as_deref() != expected {
+        return Ok(false);
+    }
+
+    let mut batch = WriteBatch::new();
+    batch.put(key, new);
+    write(db, &mut batch)?;
+    Ok(true)
+}
+
+/// Returns the value corresponding to `key` if it is stored; otherwise calls `f` , stores the
+/// return value as the value corresponding to `key` , and returns it.
+///
+/// This is useful to memoize expensive computations in the database.
+///
+/// # Warnings
+///
+/// Reading `key` and writing the value computed by `f` are not done atomically. If another thread
+/// or process writes or deletes `key` between them, `f` can be called more than once for the same
+/// `key` , or the value written by another one can be overwritten. The returned value is the
+/// return value of `f` , even if another one writes `key` after that.
+///
+/// It is an error if `db` is not opened.
+///
+/// # Examples
+///
+/// ```
+/// use mouse_leveldb::Database;
+/// use std::cell::Cell;
+/// use std::ffi::CString;
+/// use tempfile;
+///
+/// let tmp = tempfile::tempdir().unwrap();
+/// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+///
+/// let mut db = Database::new();
+/// db.open(&path).unwrap();
+///
+/// let key: &[u8] = &[1, 2, 3];
+/// let calls = Cell::new(0);
+/// let f = || {
+///     calls.set(calls.get() + 1);
+///     vec![4, 5]
+/// };
+///
+/// // 'f' is called because 'key' is not stored yet.
+/// let value = mouse_leveldb::get_or_insert_with(&db, key, f).unwrap();
+/// assert_eq!(&[4, 5], value.as_ref());
+/// assert_eq!(1, calls.get());
+///
+/// // 'f' is not called any more.
+/// let value = mouse_leveldb::get_or_insert_with(&db, key, f).unwrap();
+/// assert_eq!(&[4, 5], value.as_ref());
+/// assert_eq!(1, calls.get());
+/// ```
+#[must_use = "write errors must not be ignored"]
+pub fn get_or_insert_with<F>(db: &Database, key: &[u8], f: F) -> Result<Octets>
+where
+    F: FnOnce() -> Vec<u8>,
+{
+    if let Some(octets) = get_with_options(db, key, &READ_OPTIONS)? {
+        return Ok(octets);
+    }
+
+    let value = f();
+    let mut batch = WriteBatch::new();
+    batch.put(key, &value);
+    write(db, &mut batch)?;
+
+    Ok(Octets::from(value))
+}
+
+/// Reads the current value corresponding to `key` , passes it to `f` , and stores the return value
+/// of `f` as the new value corresponding to `key` .
+///
+/// `f` takes `None` if `key` is not stored.
+/// The new value is written with the default [`WriteOptions`] , which syncs the write for
+/// durability.
+///
+/// # Warnings
+///
+/// LevelDB is not transactional; reading and writing are not done atomically. If another thread
+/// or process writes `key` between them, the update will be lost. This function is safe only when
+/// the caller serializes the updates to `key` , for example, a single-threaded updater.
+///
+/// It is an error if `db` is not opened.
+///
+/// # Examples
+///
+/// ```
+/// use mouse_leveldb::Database;
+/// use std::ffi::CString;
+/// use tempfile;
+///
+/// let tmp = tempfile::tempdir().unwrap();
+/// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+///
+/// let mut db = Database::new();
+/// db.open(&path).unwrap();
+///
+/// let key: &[u8] = b"counter";
+/// let increment = |current: Option<&[u8]>| {
+///     let n = current.map(|v| v[0]).unwrap_or(0);
+///     vec![n + 1]
+/// };
+///
+/// mouse_leveldb::get_and_put(&db, key, increment).unwrap();
+/// assert_eq!(&[1], mouse_leveldb::get(&db, key).unwrap().as_ref());
+///
+/// mouse_leveldb::get_and_put(&db, key, increment).unwrap();
+/// assert_eq!(&[2], mouse_leveldb::get(&db, key).unwrap().as_ref());
+/// ```
+#[must_use = "write errors must not be ignored"]
+pub fn get_and_put<F>(db: &Database, key: &[u8], f: F) -> Result<()>
+where
+    F: FnOnce(Option<&[u8]>) -> Vec<u8>,
+{
+    let current = get_with_options(db, key, &READ_OPTIONS)?;
+    let new = f(current.as_deref());
+
+    let mut batch = WriteBatch::new();
+    batch.put(key, &new);
+    write(db, &mut batch)
+}
+
+/// Merges `operand` into the value corresponding to `key` by `f` on the client side.
+///
+/// `f` takes the current value (or `None` if `key` is not stored) and `operand` , and returns the
+/// new value to store. LevelDB has no merge operator, unlike RocksDB; this function is a
+/// shorthand of [`get_and_put`] to write the read-modify-write like a counter in the same
+/// manner.
+///
+/// # Warnings
+///
+/// Reading and writing are not done atomically as [`get_and_put`] . If another thread or process
+/// writes `key` between them, the update will be lost. The caller must serialize the merges to
+/// `key` .
+///
+/// It is an error if `db` is not opened.
+///
+/// # Examples
+///
+/// ```
+/// use mouse_leveldb::Database;
+/// use std::convert::TryInto;
+/// use std::ffi::CString;
+/// use tempfile;
+///
+/// let tmp = tempfile::tempdir().unwrap();
+/// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+///
+/// let mut db = Database::new();
+/// db.open(&path).unwrap();
+///
+/// let add = |current: Option<&[u8]>, operand: &[u8]| {
+///     let current = current.map_or(0, |v| u64::from_le_bytes(v.try_into().unwrap()));
+///     let operand = u64::from_le_bytes(operand.try_into().unwrap());
+///     (current + operand).to_le_bytes().to_vec()
+/// };
+///
+/// let key: &[u8] = b"counter";
+/// mouse_leveldb::merge_with(&db, key, &1_u64.to_le_bytes(), add).unwrap();
+/// assert_eq!(1, mouse_leveldb::get(&db, key).unwrap().as_u64_le().unwrap());
+///
+/// for _ in 0..10 {
+///     mouse_leveldb::merge_with(&db, key, &5_u64.to_le_bytes(), add).unwrap();
+/// }
+/// assert_eq!(51, mouse_leveldb::get(&db, key).unwrap().as_u64_le().unwrap());
+/// ```
+#[must_use = "write errors must not be ignored"]
+#[inline]
+pub fn merge_with<F>(db: &Database, key: &[u8], operand: &[u8], f: F) -> Result<()>
+where
+    F: FnOnce(Option<&[u8]>, &[u8]) -> Vec<u8>,
+{
+    get_and_put(db, key, |current| f(current, operand))
+}
+
+/// Returns the raw pointer to the `leveldb_t` which `db` wraps, or `None` if `db` is not opened.
+///
+/// This is available only if feature `raw` is enabled. It is for the advanced users who pass the
+/// database to another C library.
+///
+/// # Safety
+///
+/// `db` owns the returned pointer.
+///
+/// - The pointer is valid only while `db` is opened; [`Database::close`] or dropping `db`
+///   destroys it.
+/// - The caller must not destroy the pointer, for example, by `leveldb_close` .
+/// - `db` assumes the data is modified only via LevelDB; the caller must not break the files in
+///   the database directory.
+///
+/// # Examples
+///
+/// ```
+/// use mouse_leveldb::Database;
+/// use std::ffi::CString;
+/// use std::os::raw::c_char;
+/// use tempfile;
+///
+/// let tmp = tempfile::tempdir().unwrap();
+/// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+///
+/// let mut db = Database::new();
+/// assert_eq!(None, unsafe { mouse_leveldb::get_raw_ptr(&db) });
+///
+/// db.open(&path).unwrap();
+/// let ptr = unsafe { mouse_leveldb::get_raw_ptr(&db) }.unwrap();
+///
+/// unsafe {
+///     let name = b"leveldb.stats\0".as_ptr() as *const c_char;
+///     let stats = leveldb_sys::leveldb_property_value(ptr, name);
+///     assert_eq!(false, stats.is_null());
+///     leveldb_sys::leveldb_free(stats as *mut _);
+/// }
+/// ```
+#[cfg(feature = "raw")]
+#[inline]
+pub unsafe fn get_raw_ptr(db: &Database) -> Option<*mut leveldb_t> {
+    database::as_ptr(db)
+}
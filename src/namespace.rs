@@ -0,0 +1,229 @@
+// Copyright 2021 Shin Yoshida
+//
+// "LGPL-3.0-or-later OR Apache-2.0 OR BSD-2-Clause"
+//
+// This is part of mouse-leveldb
+//
+//  mouse-leveldb is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU Lesser General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  mouse-leveldb is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU Lesser General Public License for more details.
+//
+//  You should have received a copy of the GNU Lesser General Public License
+//  along with mouse-leveldb.  If not, see <http://www.gnu.org/licenses/>.
+//
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted
+// provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of
+//    conditions and the following disclaimer.
+// 2. Redistributions in binary form must reproduce the above copyright notice, this
+//    list of conditions and the following disclaimer in the documentation and/or other
+//    materials provided with the distribution.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+// ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+// WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+// IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT,
+// INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+// NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+
+use crate::database::{self, Database};
+use crate::error::{self, Result};
+use crate::octets::Octets;
+use crate::write_batch::WriteBatch;
+
+/// `Namespace` is a view of [`Database`] which prepends `prefix` to every key.
+///
+/// LevelDB has no column family; `Namespace` emulates it by the key prefix. `Namespace` shares the
+/// database with the original [`Database`] , and the keys out of `prefix` are invisible from it.
+///
+/// # Warnings
+///
+/// The namespaces over the same database must not have a prefix which starts with another one's
+/// prefix; otherwise, e.g. namespace `b"a"` sees the keys put via namespace `b"ab"` .
+/// Use the prefixes of the same length, or terminate each prefix by a byte which never appears in
+/// the prefixes.
+///
+/// # Examples
+///
+/// ```
+/// use mouse_leveldb::{Database, Namespace, WriteBatch};
+/// use std::ffi::CString;
+/// use tempfile;
+///
+/// let tmp = tempfile::tempdir().unwrap();
+/// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+///
+/// let mut db = Database::new();
+/// db.open(&path).unwrap();
+///
+/// let users = Namespace::new(&db, b"u:");
+/// let posts = Namespace::new(&db, b"p:");
+///
+/// users.put(&[1], &[10]).unwrap();
+/// posts.put(&[1], &[20]).unwrap();
+/// posts.put(&[2], &[21]).unwrap();
+///
+/// assert_eq!(&[10], users.get(&[1]).unwrap().as_ref());
+/// assert_eq!(&[20], posts.get(&[1]).unwrap().as_ref());
+/// assert_eq!(true, users.get(&[2]).unwrap().is_empty());
+///
+/// assert_eq!(vec![vec![1]], users.collect_keys().unwrap());
+/// assert_eq!(vec![vec![1], vec![2]], posts.collect_keys().unwrap());
+///
+/// // The underlying database holds the prefixed keys.
+/// assert_eq!(&[10], mouse_leveldb::get(&db, b"u:\x01").unwrap().as_ref());
+///
+/// // The prefix is applied in the batch, too.
+/// let mut batch = WriteBatch::new();
+/// users.delete_into(&mut batch, &[1]);
+/// posts.put_into(&mut batch, &[3], &[22]);
+/// mouse_leveldb::write(&db, &mut batch).unwrap();
+///
+/// assert_eq!(true, users.collect_keys().unwrap().is_empty());
+/// assert_eq!(
+///     vec![(vec![1], vec![20]), (vec![2], vec![21]), (vec![3], vec![22])],
+///     posts.collect_entries().unwrap()
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct Namespace<'a> {
+    db: &'a Database,
+    prefix: Vec<u8>,
+}
+
+impl<'a> Namespace<'a> {
+    /// Creates a new instance prepending `prefix` to the keys of `db` .
+    pub fn new(db: &'a Database, prefix: &[u8]) -> Self {
+        Self {
+            db,
+            prefix: prefix.to_vec(),
+        }
+    }
+
+    /// Returns the database `self` wraps.
+    #[inline]
+    pub fn database(&self) -> &'a Database {
+        self.db
+    }
+
+    /// Returns the prefix `self` prepends to the keys.
+    #[inline]
+    pub fn prefix(&self) -> &[u8] {
+        &self.prefix
+    }
+
+    /// Same to [`crate::get`] except for that `key` is prefixed.
+    ///
+    /// It is an error if the database is not opened.
+    #[must_use = "read errors must not be ignored"]
+    #[inline]
+    pub fn get(&self, key: &[u8]) -> Result<Octets> {
+        crate::get(self.db, &self.key_(key))
+    }
+
+    /// Puts `key` with the prefix and `value` into the database.
+    ///
+    /// It is an error if the database is not opened.
+    pub fn put(&self, key: &[u8], value: &[u8]) -> Result<()> {
+        let mut batch = WriteBatch::new();
+        self.put_into(&mut batch, key, value);
+        crate::write(self.db, &mut batch)
+    }
+
+    /// Deletes `key` with the prefix from the database.
+    ///
+    /// It is an error if the database is not opened.
+    pub fn delete(&self, key: &[u8]) -> Result<()> {
+        let mut batch = WriteBatch::new();
+        self.delete_into(&mut batch, key);
+        crate::write(self.db, &mut batch)
+    }
+
+    /// Appends a pair of (`key` with the prefix, `value` ) to `batch` to put.
+    ///
+    /// The operations of some namespaces can be written atomically in one `batch` .
+    #[inline]
+    pub fn put_into(&self, batch: &mut WriteBatch, key: &[u8], value: &[u8]) {
+        batch.put(&self.key_(key), value);
+    }
+
+    /// Appends `key` with the prefix to `batch` to delete.
+    ///
+    /// The operations of some namespaces can be written atomically in one `batch` .
+    #[inline]
+    pub fn delete_into(&self, batch: &mut WriteBatch, key: &[u8]) {
+        batch.delete(&self.key_(key));
+    }
+
+    /// Collects all the keys in `self` without the prefix.
+    ///
+    /// It is an error if the database is not opened.
+    pub fn collect_keys(&self) -> Result<Vec<Vec<u8>>> {
+        let mut ret = Vec::new();
+        self.for_each_(|key, _| ret.push(key.to_vec()))?;
+        Ok(ret)
+    }
+
+    /// Collects all the (key, value) pairs in `self` . The keys do not have the prefix.
+    ///
+    /// It is an error if the database is not opened.
+    #[allow(clippy::type_complexity)]
+    pub fn collect_entries(&self) -> Result<Vec<(Vec<u8>, Vec<u8>)>> {
+        let mut ret = Vec::new();
+        self.for_each_(|key, value| ret.push((key.to_vec(), value.to_vec())))?;
+        Ok(ret)
+    }
+
+    /// Calls `f` with each (key without the prefix, value) in `self` in the key order.
+    fn for_each_<F>(&self, mut f: F) -> Result<()>
+    where
+        F: FnMut(&[u8], &[u8]),
+    {
+        if database::as_ptr(self.db).is_none() {
+            return Err(error::not_opened());
+        }
+
+        let mut it = crate::iter(self.db);
+        it.seek(&self.prefix);
+        while let (Some(key), Some(value)) = (it.key(), it.value()) {
+            match key.strip_prefix(self.prefix.as_slice()) {
+                Some(key) => f(key, value),
+                None => break,
+            }
+            it.seek_next();
+        }
+        it.status()
+    }
+
+    fn key_(&self, key: &[u8]) -> Vec<u8> {
+        let mut ret = Vec::with_capacity(self.prefix.len() + key.len());
+        ret.extend_from_slice(&self.prefix);
+        ret.extend_from_slice(key);
+        ret
+    }
+}
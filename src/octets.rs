@@ -51,6 +51,10 @@
 // ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
 // POSSIBILITY OF SUCH DAMAGE.
 
+use crate::error::{self, Result};
+use crate::octets_display::{self, OctetsDisplay};
+use crate::octets_reader::{self, OctetsReader};
+use crate::shared_octets::{self, SharedOctets};
 use core::cmp::Ordering;
 use core::hash::{Hash, Hasher};
 use core::ops::{Deref, DerefMut};
@@ -58,13 +62,59 @@ use leveldb_sys::leveldb_free;
 use std::borrow::{Borrow, BorrowMut};
 use std::fmt;
 use std::os::raw::c_void;
+#[cfg(feature = "testing")]
+use std::sync::atomic::{AtomicUsize, Ordering as AtomicOrdering};
 
 /// `Octets` is a wrapper of `&mut [u8]` generated by `leveldb_sys` .
 ///
 /// User can access the wrapped value via the `Deref` and `DerefMut` implementation.
+///
+/// `Octets` implements `Clone` ; the clone owns a copy of the bytes in a `Vec` instead of the
+/// buffer LevelDB allocated, and behaves the same as the original.
+///
+/// `Octets` can be created from the bytes not fetched from the database as well; see
+/// [`Octets::empty`] , [`Octets::from_static`] , and `From<Vec<u8>>` . The comparison and the
+/// hash depend only on the bytes, whichever the origin is.
+///
+/// # Examples
+///
+/// ```
+/// use mouse_leveldb::{Database, Octets, WriteBatch};
+/// use std::collections::HashSet;
+/// use std::ffi::CString;
+/// use tempfile;
+///
+/// let tmp = tempfile::tempdir().unwrap();
+/// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+///
+/// let mut db = Database::new();
+/// db.open(&path).unwrap();
+///
+/// let mut batch = WriteBatch::new();
+/// batch.put(b"key", b"value");
+/// mouse_leveldb::write(&db, &mut batch).unwrap();
+///
+/// let mut set = HashSet::new();
+/// set.insert(mouse_leveldb::get(&db, b"key").unwrap());
+/// set.insert(mouse_leveldb::get(&db, b"missing").unwrap());
+/// assert_eq!(false, set.insert(Octets::from(b"value".to_vec())));
+/// assert_eq!(false, set.insert(Octets::from_static(b"value")));
+/// assert_eq!(false, set.insert(Octets::empty()));
+/// assert_eq!(true, set.insert(Octets::from_static(b"other")));
+/// assert_eq!(3, set.len());
+/// ```
 pub struct Octets {
-    ptr_: Option<*mut u8>,
-    len_: usize,
+    repr: Repr,
+}
+
+enum Repr {
+    // The buffer allocated by LevelDB, or a null pointer if the key is not found.
+    // It is freed by 'leveldb_free' on the drop.
+    Foreign { ptr: Option<*mut u8>, len: usize },
+    // The bytes copied by 'Clone' or given by the user.
+    Owned(Vec<u8>),
+    // The static bytes given by the user; copied into 'Owned' when modified.
+    Static(&'static [u8]),
 }
 
 unsafe impl Send for Octets {}
@@ -72,12 +122,118 @@ unsafe impl Sync for Octets {}
 
 impl Drop for Octets {
     fn drop(&mut self) {
-        if let Some(ptr) = self.ptr_ {
+        if let Repr::Foreign { ptr: Some(ptr), .. } = self.repr {
             unsafe { leveldb_free(ptr as *mut c_void) };
+            #[cfg(feature = "testing")]
+            LIVE_BUFFERS.fetch_sub(1, AtomicOrdering::SeqCst);
         }
     }
 }
 
+impl Clone for Octets {
+    /// Copies the bytes into a new instance owning them.
+    ///
+    /// The clone does not share the buffer with `self` , so it is still valid after `self` is
+    /// dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::{Database, WriteBatch};
+    /// use std::ffi::CString;
+    /// use tempfile;
+    ///
+    /// let tmp = tempfile::tempdir().unwrap();
+    /// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+    ///
+    /// let mut db = Database::new();
+    /// db.open(&path).unwrap();
+    ///
+    /// let mut batch = WriteBatch::new();
+    /// batch.put(b"key", b"value");
+    /// mouse_leveldb::write(&db, &mut batch).unwrap();
+    ///
+    /// let octets = mouse_leveldb::get(&db, b"key").unwrap();
+    /// let cloned = octets.clone();
+    /// assert_eq!(octets, cloned);
+    ///
+    /// drop(octets);
+    /// assert_eq!(b"value"[..], cloned);
+    ///
+    /// // Not found.
+    /// let octets = mouse_leveldb::get(&db, b"missing").unwrap();
+    /// let cloned = octets.clone();
+    /// assert_eq!(octets, cloned);
+    /// assert_eq!(true, cloned.as_ptr().is_null());
+    /// ```
+    ///
+    /// The clone does not hold any buffer LevelDB allocated. ( `live_octets` is available only
+    /// if feature `testing` is enabled.)
+    ///
+    /// ```
+    /// # #[cfg(feature = "testing")]
+    /// # {
+    /// use mouse_leveldb::{TestDb, WriteBatch};
+    ///
+    /// let db = TestDb::new().unwrap();
+    ///
+    /// let mut batch = WriteBatch::new();
+    /// batch.put(b"key", b"value");
+    /// mouse_leveldb::write(&db, &mut batch).unwrap();
+    ///
+    /// let before = mouse_leveldb::live_octets();
+    /// let octets = mouse_leveldb::get(&db, b"key").unwrap();
+    /// let cloned = octets.clone();
+    /// assert_eq!(before + 1, mouse_leveldb::live_octets());
+    ///
+    /// drop(octets);
+    /// assert_eq!(before, mouse_leveldb::live_octets());
+    ///
+    /// drop(cloned);
+    /// assert_eq!(before, mouse_leveldb::live_octets());
+    /// # }
+    /// ```
+    fn clone(&self) -> Self {
+        let repr = match &self.repr {
+            Repr::Foreign { ptr: None, .. } => Repr::Foreign { ptr: None, len: 0 },
+            Repr::Static(bytes) => Repr::Static(bytes),
+            _ => Repr::Owned(self.to_vec()),
+        };
+        Self { repr }
+    }
+}
+
+#[cfg(feature = "testing")]
+static LIVE_BUFFERS: AtomicUsize = AtomicUsize::new(0);
+
+/// Returns the number of the buffers which [`Octets`] holds and has not freed yet.
+///
+/// This is available only if feature `testing` is enabled.
+///
+/// # Examples
+///
+/// ```
+/// use mouse_leveldb::{TestDb, WriteBatch};
+///
+/// let db = TestDb::new().unwrap();
+///
+/// let mut batch = WriteBatch::new();
+/// batch.put(b"key", b"value");
+/// mouse_leveldb::write(&db, &mut batch).unwrap();
+///
+/// let before = mouse_leveldb::live_octets();
+///
+/// let octets = mouse_leveldb::get(&db, b"key").unwrap();
+/// assert_eq!(before + 1, mouse_leveldb::live_octets());
+///
+/// drop(octets);
+/// assert_eq!(before, mouse_leveldb::live_octets());
+/// ```
+#[cfg(feature = "testing")]
+pub fn live_octets() -> usize {
+    LIVE_BUFFERS.load(AtomicOrdering::SeqCst)
+}
+
 /// Creates a new instance.
 ///
 /// # Safety
@@ -86,15 +242,1033 @@ impl Drop for Octets {
 #[inline]
 pub unsafe fn new(ptr: *mut u8, len: usize) -> Octets {
     if ptr.is_null() {
-        assert_eq!(0, len);
+        // 'leveldb_get' always sets the length 0 with a null pointer.
+        debug_assert_eq!(0, len);
         Octets {
-            ptr_: None,
-            len_: len,
+            repr: Repr::Foreign { ptr: None, len: 0 },
         }
     } else {
+        #[cfg(feature = "testing")]
+        LIVE_BUFFERS.fetch_add(1, AtomicOrdering::SeqCst);
         Octets {
-            ptr_: Some(ptr),
-            len_: len,
+            repr: Repr::Foreign {
+                ptr: Some(ptr),
+                len,
+            },
+        }
+    }
+}
+
+/// Creates a new instance copying `bytes` .
+///
+/// The buffer is allocated by `malloc` so that `leveldb_free` can free it on the drop.
+/// It is an error if `malloc` fails.
+pub fn from_slice(bytes: &[u8]) -> Result<Octets> {
+    extern "C" {
+        fn malloc(size: usize) -> *mut c_void;
+    }
+
+    if bytes.is_empty() {
+        return Ok(unsafe { new(core::ptr::null_mut(), 0) });
+    }
+
+    unsafe {
+        let ptr = malloc(bytes.len()) as *mut u8;
+
+        #[cfg(feature = "testing")]
+        let ptr = if crate::fault::is_injected(crate::Fault::AllocationFailure) {
+            leveldb_free(ptr as *mut c_void);
+            core::ptr::null_mut()
+        } else {
+            ptr
+        };
+
+        if ptr.is_null() {
+            return Err(error::internal("failed to allocate memory to copy bytes"));
+        }
+        core::ptr::copy_nonoverlapping(bytes.as_ptr(), ptr, bytes.len());
+        Ok(new(ptr, bytes.len()))
+    }
+}
+
+impl Octets {
+    /// The number of the bytes `Debug` shows at most; `Debug` shows the length and the first
+    /// `DEBUG_PREVIEW_LEN` bytes as a byte string literal, followed by `…` if truncated.
+    ///
+    /// The printable ASCII characters are shown as they are, and the other bytes are escaped as
+    /// [`display_full`] does. Use [`display_full`] to show all the bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::Octets;
+    ///
+    /// assert_eq!(32, Octets::DEBUG_PREVIEW_LEN);
+    ///
+    /// // Empty
+    /// assert_eq!("Octets { len: 0, bytes: b\"\" }", format!("{:?}", Octets::empty()));
+    ///
+    /// // Printable
+    /// let octets = Octets::from_static(b"value");
+    /// assert_eq!("Octets { len: 5, bytes: b\"value\" }", format!("{:?}", octets));
+    ///
+    /// // Binary
+    /// let octets = Octets::from(vec![0x00, 0x7f, 0xff]);
+    /// assert_eq!(
+    ///     "Octets { len: 3, bytes: b\"\\x00\\x7f\\xff\" }",
+    ///     format!("{:?}", octets)
+    /// );
+    ///
+    /// // Mixed
+    /// let octets = Octets::from(b"k=\"v\\\"\n\x01".to_vec());
+    /// assert_eq!(
+    ///     r#"Octets { len: 8, bytes: b"k=\"v\\\"\x0a\x01" }"#,
+    ///     format!("{:?}", octets)
+    /// );
+    ///
+    /// // Longer than the bound
+    /// let octets = Octets::from(vec![b'a'; 1 << 20]);
+    /// let preview = "a".repeat(Octets::DEBUG_PREVIEW_LEN);
+    /// assert_eq!(
+    ///     format!("Octets {{ len: 1048576, bytes: b\"{}\"… }}", preview),
+    ///     format!("{:?}", octets)
+    /// );
+    ///
+    /// let octets = Octets::from(vec![0xab; Octets::DEBUG_PREVIEW_LEN + 1]);
+    /// let preview = "\\xab".repeat(Octets::DEBUG_PREVIEW_LEN);
+    /// assert_eq!(
+    ///     format!("Octets {{ len: 33, bytes: b\"{}\"… }}", preview),
+    ///     format!("{:?}", octets)
+    /// );
+    /// ```
+    ///
+    /// [`display_full`]: Self::display_full
+    pub const DEBUG_PREVIEW_LEN: usize = 32;
+
+    /// Returns a raw pointer to the wrapped buffer, or a null pointer if `self` is empty because
+    /// the key is not found.
+    ///
+    /// The pointer is valid only until `self` is dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::{Database, WriteBatch};
+    /// use std::ffi::CString;
+    /// use tempfile;
+    ///
+    /// let tmp = tempfile::tempdir().unwrap();
+    /// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+    ///
+    /// let mut db = Database::new();
+    /// db.open(&path).unwrap();
+    ///
+    /// let key: &[u8] = &[1, 2, 3];
+    /// let value: &[u8] = &[4, 5];
+    ///
+    /// let octets = mouse_leveldb::get(&db, key).unwrap();
+    /// assert_eq!(true, octets.as_ptr().is_null());
+    /// assert_eq!(0, octets.len_raw());
+    ///
+    /// let mut batch = WriteBatch::new();
+    /// batch.put(key, value);
+    /// mouse_leveldb::write(&db, &mut batch).unwrap();
+    ///
+    /// let octets = mouse_leveldb::get(&db, key).unwrap();
+    /// let bytes = unsafe { core::slice::from_raw_parts(octets.as_ptr(), octets.len_raw()) };
+    /// assert_eq!(value, bytes);
+    /// ```
+    #[inline]
+    pub fn as_ptr(&self) -> *const u8 {
+        match &self.repr {
+            Repr::Foreign { ptr, .. } => ptr.unwrap_or(core::ptr::null_mut()),
+            Repr::Owned(vec) => vec.as_ptr(),
+            Repr::Static(bytes) => bytes.as_ptr(),
+        }
+    }
+
+    /// Returns a raw mutable pointer to the wrapped buffer, or a null pointer if `self` is empty
+    /// because the key is not found.
+    ///
+    /// The pointer is valid only until `self` is dropped.
+    #[inline]
+    pub fn as_mut_ptr(&mut self) -> *mut u8 {
+        self.make_mut_();
+        match &mut self.repr {
+            Repr::Foreign { ptr, .. } => ptr.unwrap_or(core::ptr::null_mut()),
+            Repr::Owned(vec) => vec.as_mut_ptr(),
+            Repr::Static(_) => unreachable!(),
+        }
+    }
+
+    /// Returns the byte length of the wrapped buffer.
+    #[inline]
+    pub fn len_raw(&self) -> usize {
+        match &self.repr {
+            Repr::Foreign { len, .. } => *len,
+            Repr::Owned(vec) => vec.len(),
+            Repr::Static(bytes) => bytes.len(),
+        }
+    }
+
+    /// Creates a new empty instance, which is the same as the one fetched for a missing key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::{Database, Octets};
+    /// use std::ffi::CString;
+    /// use tempfile;
+    ///
+    /// let tmp = tempfile::tempdir().unwrap();
+    /// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+    ///
+    /// let mut db = Database::new();
+    /// db.open(&path).unwrap();
+    ///
+    /// const EMPTY: Octets = Octets::empty();
+    ///
+    /// let octets = EMPTY;
+    /// assert_eq!(0, octets.len());
+    /// assert_eq!(true, octets.as_ptr().is_null());
+    /// assert_eq!(mouse_leveldb::get(&db, b"missing").unwrap(), octets);
+    /// ```
+    #[inline]
+    pub const fn empty() -> Self {
+        Self {
+            repr: Repr::Foreign { ptr: None, len: 0 },
+        }
+    }
+
+    /// Creates a new instance wrapping `bytes` without copying.
+    ///
+    /// `bytes` is copied only when modified via `DerefMut` or [`as_mut_ptr`] .
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::Octets;
+    ///
+    /// let mut octets = Octets::from_static(b"value");
+    /// assert_eq!(b"value"[..], octets);
+    ///
+    /// octets[0] = b'V';
+    /// assert_eq!(b"Value"[..], octets);
+    /// ```
+    ///
+    /// [`as_mut_ptr`]: Self::as_mut_ptr
+    #[inline]
+    pub const fn from_static(bytes: &'static [u8]) -> Self {
+        Self {
+            repr: Repr::Static(bytes),
+        }
+    }
+
+    /// Creates a new instance taking the ownership of the buffer `ptr` points to.
+    ///
+    /// The buffer is freed by `leveldb_free` on the drop. A null `ptr` makes an empty instance,
+    /// which is the same as the one [`crate::get`] returns if the key is not found.
+    ///
+    /// # Safety
+    ///
+    /// - If `ptr` is null, `len` must be 0.
+    /// - If `ptr` is not null, it must point to a buffer of at least `len` bytes which
+    ///   `leveldb_free` can free; for example, a buffer `leveldb_sys::leveldb_get` returned or
+    ///   `malloc` allocated.
+    /// - The buffer must not be used nor freed by anyone else after this function is called.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::{Database, Octets, WriteBatch};
+    /// use std::ffi::CString;
+    /// use tempfile;
+    ///
+    /// let tmp = tempfile::tempdir().unwrap();
+    /// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+    ///
+    /// let mut db = Database::new();
+    /// db.open(&path).unwrap();
+    ///
+    /// let mut batch = WriteBatch::new();
+    /// batch.put(b"key", b"value");
+    /// mouse_leveldb::write(&db, &mut batch).unwrap();
+    ///
+    /// let (ptr, len) = mouse_leveldb::get(&db, b"key").unwrap().into_raw_parts();
+    /// let octets = unsafe { Octets::from_raw_parts(ptr, len) };
+    /// assert_eq!(b"value"[..], octets);
+    /// assert_eq!(ptr as *const u8, octets.as_ptr());
+    ///
+    /// let octets = unsafe { Octets::from_raw_parts(std::ptr::null_mut(), 0) };
+    /// assert_eq!(Octets::empty(), octets);
+    /// assert_eq!(true, octets.as_ptr().is_null());
+    /// ```
+    #[inline]
+    pub unsafe fn from_raw_parts(ptr: *mut u8, len: usize) -> Self {
+        new(ptr, len)
+    }
+
+    /// Returns an adapter to show all the bytes by `Display` .
+    ///
+    /// `Debug` shows only the first [`DEBUG_PREVIEW_LEN`] bytes not to flood the log; use this
+    /// method in the rare case where the whole content is wanted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::Octets;
+    ///
+    /// let octets = Octets::from(b"key=\x00\xff\"".to_vec());
+    /// assert_eq!(r#"key=\x00\xff\""#, octets.display_full().to_string());
+    ///
+    /// let octets = Octets::from(vec![b'a'; 100]);
+    /// assert_eq!("a".repeat(100), octets.display_full().to_string());
+    ///
+    /// assert_eq!("", Octets::empty().display_full().to_string());
+    /// ```
+    ///
+    /// [`DEBUG_PREVIEW_LEN`]: Self::DEBUG_PREVIEW_LEN
+    #[inline]
+    pub fn display_full(&self) -> OctetsDisplay<'_> {
+        octets_display::new(self.deref())
+    }
+
+    /// Returns the bytes in lower case hex.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::Octets;
+    ///
+    /// assert_eq!("76616c7565", Octets::from_static(b"value").to_hex());
+    /// assert_eq!("", Octets::empty().to_hex());
+    /// ```
+    #[inline]
+    pub fn to_hex(&self) -> String {
+        hex_(self, false)
+    }
+
+    /// Returns the bytes in the standard base64 with the padding (RFC 4648.)
+    ///
+    /// This is available only if feature `base64` is enabled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::Octets;
+    ///
+    /// assert_eq!("", Octets::empty().to_base64());
+    /// assert_eq!("Zg==", Octets::from_static(b"f").to_base64());
+    /// assert_eq!("Zm8=", Octets::from_static(b"fo").to_base64());
+    /// assert_eq!("Zm9v", Octets::from_static(b"foo").to_base64());
+    /// assert_eq!("Zm9vYmFy", Octets::from_static(b"foobar").to_base64());
+    /// assert_eq!("//79", Octets::from(vec![0xff, 0xfe, 0xfd]).to_base64());
+    /// ```
+    #[cfg(feature = "base64")]
+    pub fn to_base64(&self) -> String {
+        const DIGITS: &[u8; 64] =
+            b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+        let bytes = self.deref();
+        let mut ret = String::with_capacity(bytes.len().div_ceil(3) * 4);
+        for chunk in bytes.chunks(3) {
+            let b = [
+                chunk[0],
+                *chunk.get(1).unwrap_or(&0),
+                *chunk.get(2).unwrap_or(&0),
+            ];
+            let n = (b[0] as usize) << 16 | (b[1] as usize) << 8 | b[2] as usize;
+
+            ret.push(DIGITS[n >> 18 & 0x3f] as char);
+            ret.push(DIGITS[n >> 12 & 0x3f] as char);
+            if 1 < chunk.len() {
+                ret.push(DIGITS[n >> 6 & 0x3f] as char);
+            } else {
+                ret.push('=');
+            }
+            if 2 < chunk.len() {
+                ret.push(DIGITS[n & 0x3f] as char);
+            } else {
+                ret.push('=');
+            }
+        }
+        ret
+    }
+
+    /// Returns the first byte and the rest, or `None` if `self` is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::Octets;
+    ///
+    /// let octets = Octets::from_static(&[3, 1, 2, 3]);
+    /// let (tag, body) = octets.split_first().unwrap();
+    /// assert_eq!(3, *tag);
+    /// assert_eq!(&[1, 2, 3], body);
+    ///
+    /// assert_eq!(None, Octets::empty().split_first());
+    /// ```
+    #[inline]
+    pub fn split_first(&self) -> Option<(&u8, &[u8])> {
+        self.deref().split_first()
+    }
+
+    /// Divides the bytes into two at `mid` .
+    ///
+    /// The first contains `[0, mid)` and the second contains `[mid, len)` . Unlike
+    /// `<[u8]>::split_at` , this method does not panic; `mid` greater than the length is clamped
+    /// to the length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::Octets;
+    ///
+    /// let octets = Octets::from_static(&[1, 2, 3]);
+    ///
+    /// let (header, body) = octets.split_at(1);
+    /// assert_eq!(&[1], header);
+    /// assert_eq!(&[2, 3], body);
+    ///
+    /// let (header, body) = octets.split_at(10);
+    /// assert_eq!(&[1, 2, 3], header);
+    /// assert_eq!(true, body.is_empty());
+    ///
+    /// let empty = Octets::empty();
+    /// let (header, body) = empty.split_at(1);
+    /// assert_eq!(true, header.is_empty());
+    /// assert_eq!(true, body.is_empty());
+    /// ```
+    #[inline]
+    pub fn split_at(&self, mid: usize) -> (&[u8], &[u8]) {
+        let bytes = self.deref();
+        bytes.split_at(mid.min(bytes.len()))
+    }
+
+    /// Copies the bytes into an array of `N` bytes.
+    ///
+    /// It is an error if the length of `self` is not `N` . The error is
+    /// [`ErrorKind::WrongLength`] , and [`Error::length_mismatch`] tells the length of `self` .
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::{ErrorKind, Octets};
+    ///
+    /// let uuid = Octets::from((0..16).collect::<Vec<u8>>());
+    /// let array: [u8; 16] = uuid.to_array().unwrap();
+    /// assert_eq!(uuid.as_ref(), &array);
+    ///
+    /// // Too short.
+    /// let e = uuid.to_array::<17>().unwrap_err();
+    /// assert_eq!(ErrorKind::WrongLength, e.kind());
+    /// assert_eq!(Some((17, 16)), e.length_mismatch());
+    ///
+    /// // Too long.
+    /// let e = uuid.to_array::<15>().unwrap_err();
+    /// assert_eq!(Some((15, 16)), e.length_mismatch());
+    ///
+    /// // The value which is not found is empty.
+    /// let e = Octets::empty().to_array::<8>().unwrap_err();
+    /// assert_eq!(Some((8, 0)), e.length_mismatch());
+    /// assert_eq!([0_u8; 0], Octets::empty().to_array().unwrap());
+    /// ```
+    ///
+    /// [`ErrorKind::WrongLength`]: crate::ErrorKind::WrongLength
+    /// [`Error::length_mismatch`]: crate::Error::length_mismatch
+    pub fn to_array<const N: usize>(&self) -> Result<[u8; N]> {
+        let bytes = self.deref();
+        if bytes.len() != N {
+            return Err(error::wrong_length(N, bytes.len()));
+        }
+
+        let mut ret = [0; N];
+        ret.copy_from_slice(bytes);
+        Ok(ret)
+    }
+
+    /// Decodes the bytes as a big-endian `u64` .
+    ///
+    /// It is an error if the length of `self` is not 8; see [`to_array`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::Octets;
+    ///
+    /// let octets = Octets::from(vec![0, 0, 0, 0, 0, 0, 1, 2]);
+    /// assert_eq!(0x0102, octets.as_u64_be().unwrap());
+    /// assert_eq!(0x0201_0000_0000_0000, octets.as_u64_le().unwrap());
+    ///
+    /// assert_eq!(true, Octets::from(vec![1, 2]).as_u64_be().is_err());
+    /// assert_eq!(true, Octets::empty().as_u64_le().is_err());
+    /// ```
+    ///
+    /// [`to_array`]: Self::to_array
+    #[inline]
+    pub fn as_u64_be(&self) -> Result<u64> {
+        self.to_array().map(u64::from_be_bytes)
+    }
+
+    /// Decodes the bytes as a little-endian `u64` .
+    ///
+    /// It is an error if the length of `self` is not 8; see [`to_array`] for details.
+    ///
+    /// [`to_array`]: Self::to_array
+    #[inline]
+    pub fn as_u64_le(&self) -> Result<u64> {
+        self.to_array().map(u64::from_le_bytes)
+    }
+
+    /// Decodes the bytes as a big-endian `u32` .
+    ///
+    /// It is an error if the length of `self` is not 4; see [`to_array`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::Octets;
+    ///
+    /// let octets = Octets::from(vec![0, 0, 1, 2]);
+    /// assert_eq!(0x0102, octets.as_u32_be().unwrap());
+    /// assert_eq!(0x0201_0000, octets.as_u32_le().unwrap());
+    ///
+    /// let e = Octets::from(vec![0; 8]).as_u32_be().unwrap_err();
+    /// assert_eq!(Some((4, 8)), e.length_mismatch());
+    /// ```
+    ///
+    /// [`to_array`]: Self::to_array
+    #[inline]
+    pub fn as_u32_be(&self) -> Result<u32> {
+        self.to_array().map(u32::from_be_bytes)
+    }
+
+    /// Decodes the bytes as a little-endian `u32` .
+    ///
+    /// It is an error if the length of `self` is not 4; see [`to_array`] for details.
+    ///
+    /// [`to_array`]: Self::to_array
+    #[inline]
+    pub fn as_u32_le(&self) -> Result<u32> {
+        self.to_array().map(u32::from_le_bytes)
+    }
+
+    /// Creates a new [`OctetsReader`] reading the bytes from the head.
+    ///
+    /// `self` is not changed; each reader has its own position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::Octets;
+    /// use std::io::{BufRead, Read};
+    ///
+    /// // Consumes a 'BufRead' like a decompressor does.
+    /// fn consume(mut r: impl BufRead) -> Vec<u8> {
+    ///     let mut ret = Vec::new();
+    ///     loop {
+    ///         let buf = r.fill_buf().unwrap();
+    ///         if buf.is_empty() {
+    ///             return ret;
+    ///         }
+    ///         let n = buf.len().min(3);
+    ///         ret.extend_from_slice(&buf[..n]);
+    ///         r.consume(n);
+    ///     }
+    /// }
+    ///
+    /// let octets = Octets::from(b"0123456789".to_vec());
+    ///
+    /// let mut reader = octets.reader();
+    /// let mut buf = [0; 4];
+    /// assert_eq!(4, reader.read(&mut buf).unwrap());
+    /// assert_eq!(b"0123", &buf);
+    /// assert_eq!(4, reader.read(&mut buf).unwrap());
+    /// assert_eq!(b"4567", &buf);
+    /// assert_eq!(2, reader.read(&mut buf).unwrap());
+    /// assert_eq!(b"89", &buf[..2]);
+    /// assert_eq!(0, reader.read(&mut buf).unwrap());
+    ///
+    /// let mut all = Vec::new();
+    /// octets.reader().read_to_end(&mut all).unwrap();
+    /// assert_eq!(octets.as_ref(), all.as_slice());
+    ///
+    /// assert_eq!(octets.as_ref(), consume(octets.reader()).as_slice());
+    /// assert_eq!(true, consume(Octets::empty().reader()).is_empty());
+    /// ```
+    #[inline]
+    pub fn reader(&self) -> OctetsReader<'_> {
+        octets_reader::new(self.deref())
+    }
+
+    /// Copies the bytes into a new `Vec` .
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::{Database, WriteBatch};
+    /// use std::ffi::CString;
+    /// use tempfile;
+    ///
+    /// let tmp = tempfile::tempdir().unwrap();
+    /// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+    ///
+    /// let mut db = Database::new();
+    /// db.open(&path).unwrap();
+    ///
+    /// let mut batch = WriteBatch::new();
+    /// batch.put(b"key", b"value");
+    /// mouse_leveldb::write(&db, &mut batch).unwrap();
+    ///
+    /// let octets = mouse_leveldb::get(&db, b"key").unwrap();
+    /// assert_eq!(b"value".to_vec(), octets.to_vec());
+    ///
+    /// // Not found.
+    /// let octets = mouse_leveldb::get(&db, b"missing").unwrap();
+    /// assert_eq!(true, octets.to_vec().is_empty());
+    /// ```
+    #[inline]
+    pub fn to_vec(&self) -> Vec<u8> {
+        self.deref().to_vec()
+    }
+
+    /// Copies the bytes into a new `Vec` and frees the buffer LevelDB allocated at once.
+    ///
+    /// `Vec<u8>` implements `From<Octets>` as well.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::{Database, WriteBatch};
+    /// use std::ffi::CString;
+    /// use tempfile;
+    ///
+    /// let tmp = tempfile::tempdir().unwrap();
+    /// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+    ///
+    /// let mut db = Database::new();
+    /// db.open(&path).unwrap();
+    ///
+    /// let large = vec![0xab; 1 << 20];
+    ///
+    /// let mut batch = WriteBatch::new();
+    /// batch.put(b"large", &large);
+    /// batch.put(b"empty", &[]);
+    /// mouse_leveldb::write(&db, &mut batch).unwrap();
+    ///
+    /// assert_eq!(large, mouse_leveldb::get(&db, b"large").unwrap().into_vec());
+    /// assert_eq!(Vec::<u8>::new(), mouse_leveldb::get(&db, b"empty").unwrap().into_vec());
+    ///
+    /// let vec: Vec<u8> = mouse_leveldb::get(&db, b"large").unwrap().into();
+    /// assert_eq!(large, vec);
+    /// ```
+    ///
+    /// The buffer is freed exactly once. ( `live_octets` is available only if feature `testing`
+    /// is enabled.)
+    ///
+    /// ```
+    /// # #[cfg(feature = "testing")]
+    /// # {
+    /// use mouse_leveldb::{TestDb, WriteBatch};
+    ///
+    /// let db = TestDb::new().unwrap();
+    ///
+    /// let mut batch = WriteBatch::new();
+    /// batch.put(b"key", b"value");
+    /// mouse_leveldb::write(&db, &mut batch).unwrap();
+    ///
+    /// let before = mouse_leveldb::live_octets();
+    /// let octets = mouse_leveldb::get(&db, b"key").unwrap();
+    /// assert_eq!(before + 1, mouse_leveldb::live_octets());
+    ///
+    /// let vec = octets.into_vec();
+    /// assert_eq!(b"value".to_vec(), vec);
+    /// assert_eq!(before, mouse_leveldb::live_octets());
+    /// # }
+    /// ```
+    #[inline]
+    pub fn into_vec(mut self) -> Vec<u8> {
+        match &mut self.repr {
+            Repr::Owned(vec) => core::mem::take(vec),
+            // 'self' is dropped and frees the buffer after copied.
+            Repr::Foreign { .. } | Repr::Static(_) => self.to_vec(),
+        }
+    }
+
+    /// Gives up the ownership of the buffer and returns the pointer to it and the length.
+    ///
+    /// The caller is responsible for freeing the buffer by `leveldb_free` , or passing it to
+    /// [`from_raw_parts`] . The pointer is null and the length is 0 if `self` is empty because
+    /// the key is not found.
+    ///
+    /// The buffer LevelDB allocated is returned without copying; the other bytes (for example,
+    /// the ones given by `From<Vec<u8>>` ) are copied into a buffer `leveldb_free` can free.
+    /// Aborts the process if it fails to allocate the buffer, as `Vec` does.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::{Database, Octets, WriteBatch};
+    /// use std::ffi::CString;
+    /// use std::os::raw::c_void;
+    /// use tempfile;
+    ///
+    /// let tmp = tempfile::tempdir().unwrap();
+    /// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+    ///
+    /// let mut db = Database::new();
+    /// db.open(&path).unwrap();
+    ///
+    /// let mut batch = WriteBatch::new();
+    /// batch.put(b"key", b"value");
+    /// mouse_leveldb::write(&db, &mut batch).unwrap();
+    ///
+    /// let octets = mouse_leveldb::get(&db, b"key").unwrap();
+    /// let before = octets.as_ptr();
+    /// let (ptr, len) = octets.into_raw_parts();
+    /// assert_eq!(before, ptr as *const u8);
+    /// assert_eq!(b"value", unsafe { std::slice::from_raw_parts(ptr, len) });
+    /// unsafe { leveldb_sys::leveldb_free(ptr as *mut c_void) };
+    ///
+    /// let (ptr, len) = Octets::from(b"owned".to_vec()).into_raw_parts();
+    /// assert_eq!(b"owned"[..], unsafe { Octets::from_raw_parts(ptr, len) });
+    ///
+    /// let (ptr, len) = Octets::empty().into_raw_parts();
+    /// assert_eq!(true, ptr.is_null());
+    /// assert_eq!(0, len);
+    /// ```
+    ///
+    /// [`from_raw_parts`]: Self::from_raw_parts
+    pub fn into_raw_parts(mut self) -> (*mut u8, usize) {
+        if let Repr::Foreign { ptr, len } = &mut self.repr {
+            let len = core::mem::take(len);
+            return match ptr.take() {
+                None => (core::ptr::null_mut(), 0),
+                Some(ptr) => {
+                    #[cfg(feature = "testing")]
+                    LIVE_BUFFERS.fetch_sub(1, AtomicOrdering::SeqCst);
+                    (ptr, len)
+                }
+            };
+        }
+
+        let copied = from_slice(self.deref()).unwrap_or_else(|_| {
+            std::alloc::handle_alloc_error(std::alloc::Layout::array::<u8>(self.len()).unwrap())
+        });
+        copied.into_raw_parts()
+    }
+
+    /// Consumes and leaks `self` , returning a mutable reference to the bytes.
+    ///
+    /// The buffer is never freed. The bytes given by [`from_static`] are copied into a new buffer
+    /// to be mutable; the other bytes are not copied.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::{Database, Octets, WriteBatch};
+    /// use std::ffi::CString;
+    /// use tempfile;
+    ///
+    /// let tmp = tempfile::tempdir().unwrap();
+    /// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+    ///
+    /// let mut db = Database::new();
+    /// db.open(&path).unwrap();
+    ///
+    /// let mut batch = WriteBatch::new();
+    /// batch.put(b"key", b"value");
+    /// mouse_leveldb::write(&db, &mut batch).unwrap();
+    ///
+    /// let bytes: &'static mut [u8] = mouse_leveldb::get(&db, b"key").unwrap().leak();
+    /// bytes[0] = b'V';
+    /// assert_eq!(b"Value", bytes);
+    ///
+    /// assert_eq!(true, Octets::empty().leak().is_empty());
+    /// assert_eq!(b"static", Octets::from_static(b"static").leak());
+    /// ```
+    ///
+    /// [`from_static`]: Self::from_static
+    pub fn leak(mut self) -> &'static mut [u8] {
+        self.make_mut_();
+        match &mut self.repr {
+            Repr::Owned(vec) => core::mem::take(vec).leak(),
+            _ => match self.into_raw_parts() {
+                (ptr, _) if ptr.is_null() => &mut [],
+                (ptr, len) => unsafe { core::slice::from_raw_parts_mut(ptr, len) },
+            },
+        }
+    }
+
+    /// Converts `self` into `bytes::Bytes` without copying the bytes.
+    ///
+    /// The buffer LevelDB allocated is freed when the last `Bytes` sharing it is dropped.
+    /// The empty `self` is converted into `Bytes::new()` .
+    ///
+    /// This is available only if feature `bytes` is enabled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::Octets;
+    ///
+    /// let bytes = Octets::from(b"value".to_vec()).into_bytes();
+    /// assert_eq!(b"value", &bytes[..]);
+    /// assert_eq!(b"alu", &bytes.slice(1..4)[..]);
+    ///
+    /// assert_eq!(true, Octets::empty().into_bytes().is_empty());
+    /// ```
+    ///
+    /// The buffer LevelDB allocated is not copied, and is freed exactly once. ( `live_octets` is
+    /// available only if feature `testing` is enabled.)
+    ///
+    /// ```
+    /// # #[cfg(feature = "testing")]
+    /// # {
+    /// use mouse_leveldb::{TestDb, WriteBatch};
+    /// use std::thread;
+    ///
+    /// let db = TestDb::new().unwrap();
+    ///
+    /// let mut batch = WriteBatch::new();
+    /// batch.put(b"key", b"value");
+    /// mouse_leveldb::write(&db, &mut batch).unwrap();
+    ///
+    /// let before = mouse_leveldb::live_octets();
+    /// let octets = mouse_leveldb::get(&db, b"key").unwrap();
+    /// let ptr = octets.as_ptr();
+    ///
+    /// let bytes = octets.into_bytes();
+    /// assert_eq!(ptr, bytes.as_ptr());
+    /// assert_eq!(before + 1, mouse_leveldb::live_octets());
+    ///
+    /// let tail = bytes.slice(2..);
+    /// drop(bytes);
+    /// assert_eq!(before + 1, mouse_leveldb::live_octets());
+    ///
+    /// let tail = thread::spawn(move || {
+    ///     assert_eq!(b"lue", &tail[..]);
+    ///     tail
+    /// })
+    /// .join()
+    /// .unwrap();
+    ///
+    /// drop(tail);
+    /// assert_eq!(before, mouse_leveldb::live_octets());
+    /// # }
+    /// ```
+    #[cfg(feature = "bytes")]
+    pub fn into_bytes(mut self) -> bytes::Bytes {
+        if self.is_empty() {
+            return bytes::Bytes::new();
+        }
+
+        match &mut self.repr {
+            Repr::Owned(vec) => bytes::Bytes::from(core::mem::take(vec)),
+            Repr::Static(slice) => bytes::Bytes::from_static(slice),
+            Repr::Foreign { .. } => bytes::Bytes::from_owner(self),
+        }
+    }
+
+    /// Converts `self` into [`SharedOctets`] to hand out some views of the bytes without copying
+    /// them.
+    ///
+    /// See [`SharedOctets`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::Octets;
+    ///
+    /// let shared = Octets::from(vec![1, 2, 3]).into_shared();
+    /// assert_eq!(&[1, 2, 3], shared.as_ref());
+    /// assert_eq!(&[2], shared.slice(1..2).as_ref());
+    /// ```
+    #[inline]
+    pub fn into_shared(self) -> SharedOctets {
+        shared_octets::new(self)
+    }
+
+    /// Converts `self` into the bytes owned by a `Vec` if not yet, and returns a mutable reference
+    /// to the `Vec` .
+    ///
+    /// The buffer LevelDB allocated is copied into the `Vec` and freed. Modifying the bytes
+    /// without changing the length via `DerefMut` does not need this method; it edits the buffer
+    /// in place.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::{Database, WriteBatch};
+    /// use std::ffi::CString;
+    /// use tempfile;
+    ///
+    /// let tmp = tempfile::tempdir().unwrap();
+    /// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+    ///
+    /// let mut db = Database::new();
+    /// db.open(&path).unwrap();
+    ///
+    /// let mut batch = WriteBatch::new();
+    /// batch.put(b"key", b"value");
+    /// mouse_leveldb::write(&db, &mut batch).unwrap();
+    ///
+    /// let mut octets = mouse_leveldb::get(&db, b"key").unwrap();
+    /// let foreign = octets.as_ptr();
+    ///
+    /// // Editing in place does not copy.
+    /// octets[0] = b'V';
+    /// assert_eq!(foreign, octets.as_ptr());
+    ///
+    /// let vec = octets.make_mut();
+    /// vec.insert(0, b'_');
+    /// assert_eq!(b"_Value"[..], octets);
+    /// assert_ne!(foreign, octets.as_ptr());
+    /// ```
+    ///
+    /// The buffer LevelDB allocated is freed when copied. ( `live_octets` is available only if
+    /// feature `testing` is enabled.)
+    ///
+    /// ```
+    /// # #[cfg(feature = "testing")]
+    /// # {
+    /// use mouse_leveldb::{TestDb, WriteBatch};
+    ///
+    /// let db = TestDb::new().unwrap();
+    ///
+    /// let mut batch = WriteBatch::new();
+    /// batch.put(b"key", b"value");
+    /// mouse_leveldb::write(&db, &mut batch).unwrap();
+    ///
+    /// let before = mouse_leveldb::live_octets();
+    /// let mut octets = mouse_leveldb::get(&db, b"key").unwrap();
+    /// octets.truncate(3);
+    /// octets[0] = b'V';
+    /// assert_eq!(before + 1, mouse_leveldb::live_octets());
+    ///
+    /// octets.push(b'!');
+    /// assert_eq!(before, mouse_leveldb::live_octets());
+    /// assert_eq!(b"Val!"[..], octets);
+    ///
+    /// octets.make_mut().clear();
+    /// assert_eq!(before, mouse_leveldb::live_octets());
+    /// # }
+    /// ```
+    pub fn make_mut(&mut self) -> &mut Vec<u8> {
+        if !matches!(self.repr, Repr::Owned(_)) {
+            // Replaces 'self' (not only 'self.repr' ) to free the buffer LevelDB allocated.
+            *self = Self {
+                repr: Repr::Owned(self.to_vec()),
+            };
+        }
+
+        match &mut self.repr {
+            Repr::Owned(vec) => vec,
+            _ => unreachable!(),
+        }
+    }
+
+    /// Appends `byte` to the end.
+    ///
+    /// The bytes are copied into a `Vec` unless they are already; see [`make_mut`] .
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::Octets;
+    ///
+    /// let mut octets = Octets::from_static(b"ab");
+    /// octets.push(b'c');
+    /// assert_eq!(b"abc"[..], octets);
+    ///
+    /// let mut octets = Octets::empty();
+    /// octets.push(0);
+    /// assert_eq!([0][..], octets);
+    /// ```
+    ///
+    /// [`make_mut`]: Self::make_mut
+    #[inline]
+    pub fn push(&mut self, byte: u8) {
+        self.make_mut().push(byte);
+    }
+
+    /// Appends `bytes` to the end.
+    ///
+    /// The bytes are copied into a `Vec` unless they are already; see [`make_mut`] . Nothing is
+    /// copied if `bytes` is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::Octets;
+    ///
+    /// let mut octets = Octets::from_static(b"ab");
+    /// octets.extend_from_slice(b"");
+    /// assert_eq!(b"ab".as_ptr(), octets.as_ptr());
+    ///
+    /// octets.extend_from_slice(b"cd");
+    /// assert_eq!(b"abcd"[..], octets);
+    /// ```
+    ///
+    /// [`make_mut`]: Self::make_mut
+    pub fn extend_from_slice(&mut self, bytes: &[u8]) {
+        if !bytes.is_empty() {
+            self.make_mut().extend_from_slice(bytes);
+        }
+    }
+
+    /// Shortens the bytes to keep the first `len` bytes and drops the rest.
+    ///
+    /// Does nothing if `len` is greater than or equal to the current length. Unlike [`push`] and
+    /// [`extend_from_slice`] , this method never copies the bytes; the buffer LevelDB allocated
+    /// is kept until `self` is dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::{Database, WriteBatch};
+    /// use std::ffi::CString;
+    /// use tempfile;
+    ///
+    /// let tmp = tempfile::tempdir().unwrap();
+    /// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+    ///
+    /// let mut db = Database::new();
+    /// db.open(&path).unwrap();
+    ///
+    /// let mut batch = WriteBatch::new();
+    /// batch.put(b"key", b"value");
+    /// mouse_leveldb::write(&db, &mut batch).unwrap();
+    ///
+    /// let mut octets = mouse_leveldb::get(&db, b"key").unwrap();
+    /// let foreign = octets.as_ptr();
+    ///
+    /// octets.truncate(10);
+    /// assert_eq!(b"value"[..], octets);
+    ///
+    /// octets.truncate(2);
+    /// assert_eq!(b"va"[..], octets);
+    /// assert_eq!(foreign, octets.as_ptr());
+    ///
+    /// octets.truncate(0);
+    /// assert_eq!(true, octets.is_empty());
+    /// ```
+    ///
+    /// [`push`]: Self::push
+    /// [`extend_from_slice`]: Self::extend_from_slice
+    pub fn truncate(&mut self, len: usize) {
+        match &mut self.repr {
+            Repr::Foreign { len: current, .. } => *current = len.min(*current),
+            Repr::Owned(vec) => vec.truncate(len),
+            Repr::Static(bytes) => *bytes = &bytes[..len.min(bytes.len())],
+        }
+    }
+
+    /// Copies the static bytes into a `Vec` to modify them.
+    fn make_mut_(&mut self) {
+        if let Repr::Static(bytes) = self.repr {
+            self.repr = Repr::Owned(bytes.to_vec());
         }
     }
 }
@@ -110,6 +1284,150 @@ impl PartialEq<Self> for Octets {
 
 impl Eq for Octets {}
 
+impl PartialEq<[u8]> for Octets {
+    /// Compares the bytes of `self` with `other` .
+    ///
+    /// `Octets` can be compared with `[u8]` , `&[u8]` , and `Vec<u8>` , in both directions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::{Database, WriteBatch};
+    /// use std::ffi::CString;
+    /// use tempfile;
+    ///
+    /// let tmp = tempfile::tempdir().unwrap();
+    /// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+    ///
+    /// let mut db = Database::new();
+    /// db.open(&path).unwrap();
+    ///
+    /// let mut batch = WriteBatch::new();
+    /// batch.put(b"key", b"value");
+    /// mouse_leveldb::write(&db, &mut batch).unwrap();
+    ///
+    /// let octets = mouse_leveldb::get(&db, b"key").unwrap();
+    ///
+    /// assert_eq!(octets, b"value"[..]);
+    /// assert_eq!(b"value"[..], octets);
+    ///
+    /// let value: &[u8] = b"value";
+    /// assert_eq!(octets, value);
+    /// assert_eq!(value, octets);
+    ///
+    /// assert_ne!(octets, b"other"[..]);
+    /// ```
+    #[inline]
+    fn eq(&self, other: &[u8]) -> bool {
+        let this: &[u8] = self.borrow();
+        this.eq(other)
+    }
+}
+
+impl PartialEq<&[u8]> for Octets {
+    #[inline]
+    fn eq(&self, other: &&[u8]) -> bool {
+        self.eq(*other)
+    }
+}
+
+impl PartialEq<Octets> for [u8] {
+    #[inline]
+    fn eq(&self, other: &Octets) -> bool {
+        other.eq(self)
+    }
+}
+
+impl PartialEq<Octets> for &[u8] {
+    #[inline]
+    fn eq(&self, other: &Octets) -> bool {
+        other.eq(*self)
+    }
+}
+
+impl PartialEq<Vec<u8>> for Octets {
+    #[inline]
+    fn eq(&self, other: &Vec<u8>) -> bool {
+        self.eq(other.as_slice())
+    }
+}
+
+impl PartialEq<Octets> for Vec<u8> {
+    #[inline]
+    fn eq(&self, other: &Octets) -> bool {
+        other.eq(self.as_slice())
+    }
+}
+
+impl PartialOrd<[u8]> for Octets {
+    /// Compares the bytes of `self` with `other` in the lexicographic order as `[u8]` does.
+    ///
+    /// `Octets` can be compared with `[u8]` , `&[u8]` , and `Vec<u8>` , in both directions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::Octets;
+    ///
+    /// let octets = Octets::from_static(&[1, 2]);
+    ///
+    /// let less: &[u8] = &[1, 1, 255];
+    /// let greater: &[u8] = &[1, 2, 0];
+    ///
+    /// assert!(octets > *less);
+    /// assert!(*less < octets);
+    /// assert!(octets < greater);
+    /// assert!(greater > octets);
+    /// assert!(octets <= vec![1, 2]);
+    /// assert!(vec![1, 2] >= octets);
+    ///
+    /// assert_eq!(octets, vec![1, 2]);
+    /// assert_eq!(vec![1, 2], octets);
+    /// assert_ne!(octets, vec![1, 2, 0]);
+    /// assert_ne!(Vec::<u8>::new(), octets);
+    /// assert_eq!(Octets::empty(), Vec::new());
+    /// ```
+    #[inline]
+    fn partial_cmp(&self, other: &[u8]) -> Option<Ordering> {
+        self.deref().partial_cmp(other)
+    }
+}
+
+impl PartialOrd<&[u8]> for Octets {
+    #[inline]
+    fn partial_cmp(&self, other: &&[u8]) -> Option<Ordering> {
+        self.deref().partial_cmp(*other)
+    }
+}
+
+impl PartialOrd<Vec<u8>> for Octets {
+    #[inline]
+    fn partial_cmp(&self, other: &Vec<u8>) -> Option<Ordering> {
+        self.deref().partial_cmp(other.as_slice())
+    }
+}
+
+impl PartialOrd<Octets> for [u8] {
+    #[inline]
+    fn partial_cmp(&self, other: &Octets) -> Option<Ordering> {
+        self.partial_cmp(other.deref())
+    }
+}
+
+impl PartialOrd<Octets> for &[u8] {
+    #[inline]
+    fn partial_cmp(&self, other: &Octets) -> Option<Ordering> {
+        (*self).partial_cmp(other.deref())
+    }
+}
+
+impl PartialOrd<Octets> for Vec<u8> {
+    #[inline]
+    fn partial_cmp(&self, other: &Octets) -> Option<Ordering> {
+        self.as_slice().partial_cmp(other.deref())
+    }
+}
+
 impl PartialOrd<Self> for Octets {
     #[inline]
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
@@ -140,11 +1458,84 @@ impl Hash for Octets {
 }
 
 impl fmt::Debug for Octets {
+    /// Shows the length and the first [`DEBUG_PREVIEW_LEN`] bytes.
+    ///
+    /// [`DEBUG_PREVIEW_LEN`]: Self::DEBUG_PREVIEW_LEN
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        debug_fmt(f, "Octets", self.deref())
+    }
+}
+
+impl fmt::LowerHex for Octets {
+    /// Writes each byte in 2 lower case hex digits; the alternate flag (`{:#x}` ) prefixes `0x` .
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::Octets;
+    ///
+    /// let octets = Octets::from(vec![0x00, 0x0f, 0xab, 0xff]);
+    /// assert_eq!("000fabff", format!("{:x}", octets));
+    /// assert_eq!("0x000fabff", format!("{:#x}", octets));
+    /// assert_eq!("000FABFF", format!("{:X}", octets));
+    /// assert_eq!("0x000FABFF", format!("{:#X}", octets));
+    /// assert_eq!("", format!("{:x}", Octets::empty()));
+    ///
+    /// // The same as formatting each byte.
+    /// let bytes: Vec<u8> = (0..=255).collect();
+    /// let expected: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+    /// assert_eq!(expected, format!("{:x}", Octets::from(bytes)));
+    /// ```
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_tuple("Octets").field(&self.deref()).finish()
+        if f.alternate() {
+            f.write_str("0x")?;
+        }
+        f.write_str(&hex_(self, false))
     }
 }
 
+impl fmt::UpperHex for Octets {
+    /// Writes each byte in 2 upper case hex digits; the alternate flag (`{:#X}` ) prefixes `0x` .
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            f.write_str("0x")?;
+        }
+        f.write_str(&hex_(self, true))
+    }
+}
+
+/// Writes `name` , the length of `bytes` , and the first [`Octets::DEBUG_PREVIEW_LEN`] bytes
+/// into `f` .
+pub fn debug_fmt(f: &mut fmt::Formatter<'_>, name: &str, bytes: &[u8]) -> fmt::Result {
+    let preview = &bytes[..bytes.len().min(Octets::DEBUG_PREVIEW_LEN)];
+    let ellipsis = if preview.len() < bytes.len() {
+        "…"
+    } else {
+        ""
+    };
+
+    f.debug_struct(name)
+        .field("len", &bytes.len())
+        .field(
+            "bytes",
+            &format_args!("{:?}{}", octets_display::new(preview), ellipsis),
+        )
+        .finish()
+}
+
+fn hex_(bytes: &[u8], upper: bool) -> String {
+    const LOWER: &[u8; 16] = b"0123456789abcdef";
+    const UPPER: &[u8; 16] = b"0123456789ABCDEF";
+    let digits = if upper { UPPER } else { LOWER };
+
+    let mut ret = String::with_capacity(bytes.len() * 2);
+    for b in bytes {
+        ret.push(digits[(b >> 4) as usize] as char);
+        ret.push(digits[(b & 0x0f) as usize] as char);
+    }
+    ret
+}
+
 impl AsRef<[u8]> for Octets {
     #[inline]
     fn as_ref(&self) -> &[u8] {
@@ -178,9 +1569,14 @@ impl Deref for Octets {
 
     #[inline]
     fn deref(&self) -> &Self::Target {
-        match self.ptr_ {
-            None => &[],
-            Some(ptr) => unsafe { core::slice::from_raw_parts(ptr, self.len_) },
+        match &self.repr {
+            Repr::Foreign { ptr: None, .. } => &[],
+            Repr::Foreign {
+                ptr: Some(ptr),
+                len,
+            } => unsafe { core::slice::from_raw_parts(*ptr, *len) },
+            Repr::Owned(vec) => vec,
+            Repr::Static(bytes) => bytes,
         }
     }
 }
@@ -188,9 +1584,225 @@ impl Deref for Octets {
 impl DerefMut for Octets {
     #[inline]
     fn deref_mut(&mut self) -> &mut Self::Target {
-        match self.ptr_ {
-            None => &mut [],
-            Some(ptr) => unsafe { core::slice::from_raw_parts_mut(ptr, self.len_) },
+        self.make_mut_();
+        match &mut self.repr {
+            Repr::Foreign { ptr: None, .. } => &mut [],
+            Repr::Foreign {
+                ptr: Some(ptr),
+                len,
+            } => unsafe { core::slice::from_raw_parts_mut(*ptr, *len) },
+            Repr::Owned(vec) => vec,
+            Repr::Static(_) => unreachable!(),
+        }
+    }
+}
+
+impl IntoIterator for Octets {
+    type Item = u8;
+    type IntoIter = std::vec::IntoIter<u8>;
+
+    /// Copies the bytes out and frees the buffer LevelDB allocated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::{Database, WriteBatch};
+    /// use std::ffi::CString;
+    /// use tempfile;
+    ///
+    /// let tmp = tempfile::tempdir().unwrap();
+    /// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+    ///
+    /// let mut db = Database::new();
+    /// db.open(&path).unwrap();
+    ///
+    /// let mut batch = WriteBatch::new();
+    /// batch.put(b"key", &[1, 2, 3, 4]);
+    /// mouse_leveldb::write(&db, &mut batch).unwrap();
+    ///
+    /// let octets = mouse_leveldb::get(&db, b"key").unwrap();
+    /// let mut sum = 0;
+    /// for b in octets {
+    ///     sum += b;
+    /// }
+    /// assert_eq!(10, sum);
+    ///
+    /// // Not found.
+    /// let octets = mouse_leveldb::get(&db, b"missing").unwrap();
+    /// assert_eq!(0, octets.into_iter().count());
+    /// ```
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.into_vec().into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Octets {
+    type Item = &'a u8;
+    type IntoIter = core::slice::Iter<'a, u8>;
+
+    /// Iterates the bytes without copying.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::{Database, WriteBatch};
+    /// use std::ffi::CString;
+    /// use tempfile;
+    ///
+    /// let tmp = tempfile::tempdir().unwrap();
+    /// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+    ///
+    /// let mut db = Database::new();
+    /// db.open(&path).unwrap();
+    ///
+    /// let mut batch = WriteBatch::new();
+    /// batch.put(b"key", &[1, 2, 3, 4]);
+    /// mouse_leveldb::write(&db, &mut batch).unwrap();
+    ///
+    /// let octets = mouse_leveldb::get(&db, b"key").unwrap();
+    /// let mut sum = 0;
+    /// for b in &octets {
+    ///     sum += *b;
+    /// }
+    /// assert_eq!(10, sum);
+    /// assert_eq!(4, octets.len());
+    /// ```
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.deref().iter()
+    }
+}
+
+impl From<Octets> for Vec<u8> {
+    /// Same to [`Octets::into_vec`] .
+    #[inline]
+    fn from(octets: Octets) -> Self {
+        octets.into_vec()
+    }
+}
+
+impl From<Vec<u8>> for Octets {
+    /// Creates a new instance owning `vec` without copying.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::Octets;
+    ///
+    /// let octets = Octets::from(vec![1, 2, 3]);
+    /// assert_eq!(&[1, 2, 3], octets.as_ref());
+    /// assert_eq!(vec![1, 2, 3], octets.into_vec());
+    /// ```
+    #[inline]
+    fn from(vec: Vec<u8>) -> Self {
+        Self {
+            repr: Repr::Owned(vec),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde_crate::Serialize for Octets {
+    /// Serializes the bytes by `serialize_bytes` .
+    ///
+    /// This is available only if feature `serde` is enabled.
+    ///
+    /// The representation is the same as crate `serde_bytes` ; for example, `bincode` writes the
+    /// length followed by the raw bytes, and `serde_json` writes an array of the numbers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::Octets;
+    ///
+    /// let values = vec![
+    ///     Octets::from_static(b"value"),
+    ///     Octets::empty(),
+    ///     Octets::from(vec![0xff, 0x00, 0xfe]),
+    /// ];
+    ///
+    /// for value in values {
+    ///     let bytes = bincode::serialize(&value).unwrap();
+    ///     assert_eq!(value, bincode::deserialize::<Octets>(&bytes).unwrap());
+    ///
+    ///     let json = serde_json::to_string(&value).unwrap();
+    ///     assert_eq!(value, serde_json::from_str::<Octets>(&json).unwrap());
+    /// }
+    ///
+    /// let json = serde_json::to_string(&Octets::from(vec![0xff, 0x00])).unwrap();
+    /// assert_eq!("[255,0]", json);
+    /// ```
+    fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
+    where
+        S: serde_crate::Serializer,
+    {
+        serializer.serialize_bytes(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde_crate::Deserialize<'de> for Octets {
+    /// Deserializes the bytes serialized by the `Serialize` implementation into a new instance
+    /// owning them.
+    ///
+    /// This is available only if feature `serde` is enabled.
+    fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
+    where
+        D: serde_crate::Deserializer<'de>,
+    {
+        deserializer.deserialize_byte_buf(OctetsVisitor)
+    }
+}
+
+#[cfg(feature = "serde")]
+struct OctetsVisitor;
+
+#[cfg(feature = "serde")]
+impl<'de> serde_crate::de::Visitor<'de> for OctetsVisitor {
+    type Value = Octets;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("bytes")
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> core::result::Result<Self::Value, E>
+    where
+        E: serde_crate::de::Error,
+    {
+        Ok(Octets::from(v.to_vec()))
+    }
+
+    fn visit_byte_buf<E>(self, v: Vec<u8>) -> core::result::Result<Self::Value, E>
+    where
+        E: serde_crate::de::Error,
+    {
+        Ok(Octets::from(v))
+    }
+
+    fn visit_str<E>(self, v: &str) -> core::result::Result<Self::Value, E>
+    where
+        E: serde_crate::de::Error,
+    {
+        self.visit_bytes(v.as_bytes())
+    }
+
+    fn visit_string<E>(self, v: String) -> core::result::Result<Self::Value, E>
+    where
+        E: serde_crate::de::Error,
+    {
+        self.visit_byte_buf(v.into_bytes())
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> core::result::Result<Self::Value, A::Error>
+    where
+        A: serde_crate::de::SeqAccess<'de>,
+    {
+        // Do not trust the size hint too much to allocate.
+        let mut vec = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(4096));
+        while let Some(b) = seq.next_element()? {
+            vec.push(b);
         }
+        Ok(Octets::from(vec))
     }
 }
@@ -51,19 +51,132 @@
 // ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
 // POSSIBILITY OF SUCH DAMAGE.
 
+use crate::cache::Cache;
+use crate::env::Env;
+use crate::filter_policy::FilterPolicy;
 use core::ptr::NonNull;
 use leveldb_sys::*;
-use std::os::raw::c_uchar;
+use std::fmt;
+use std::os::raw::{c_int, c_uchar};
 
 /// `Options` is a wrapper of `*mut leveldb_options_t` to make sure to destruct on the drop.
-pub struct Options(NonNull<leveldb_options_t>);
+///
+/// `Options` owns the objects set to it (for example, [`Env`] ,) because LevelDB requires them
+/// to outlive the database.
+///
+/// LevelDB writes the internal log into file `LOG` in the database directory. `Options` does not
+/// provide the way to route or to silence the log, because the C API of LevelDB has no function
+/// to create a custom logger; `leveldb_options_set_info_log` accepts only a logger created
+/// elsewhere, and falls back to file `LOG` if the logger is null.
+/// [`Database::info_log_path`] returns the path to the log file.
+///
+/// [`Database::info_log_path`]: crate::Database::info_log_path
+pub struct Options {
+    ptr: NonNull<leveldb_options_t>,
+    env: Option<Env>,
+    cache: Option<Cache>,
+    filter_policy: Option<FilterPolicy>,
+    // The values set to 'ptr' to report.
+    create_if_missing: bool,
+    compression: bool,
+    max_file_size: Option<usize>,
+}
 
 unsafe impl Send for Options {}
 unsafe impl Sync for Options {}
 
 impl Drop for Options {
     fn drop(&mut self) {
-        unsafe { leveldb_options_destroy(self.0.as_ptr()) };
+        unsafe { leveldb_options_destroy(self.ptr.as_ptr()) };
+    }
+}
+
+impl fmt::Debug for Options {
+    /// Shows the configuration without the pointer values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::Options;
+    ///
+    /// let mut options = Options::new();
+    /// options.set_bloom_filter(10);
+    ///
+    /// assert_eq!(
+    ///     "Options { create_if_missing: true, compression: true, max_file_size: None, \
+    ///      bloom_filter: Some(10), cache: false, env: false }",
+    ///     format!("{:?}", options),
+    /// );
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let bloom_filter = self.filter_policy.as_ref().map(FilterPolicy::bits_per_key);
+        f.debug_struct("Options")
+            .field("create_if_missing", &self.create_if_missing)
+            .field("compression", &self.compression)
+            .field("max_file_size", &self.max_file_size)
+            .field("bloom_filter", &bloom_filter)
+            .field("cache", &self.cache.is_some())
+            .field("env", &self.env.is_some())
+            .finish()
+    }
+}
+
+impl Clone for Options {
+    /// Creates a new instance with the same configuration as `self` .
+    ///
+    /// The clone shares the [`Cache`] with `self` if any; the other objects, that is, the bloom
+    /// filter and the [`Env`] , are created again because they are not shareable.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::{Database, Env, Options};
+    /// use std::ffi::CString;
+    /// use tempfile;
+    ///
+    /// let tmp1 = tempfile::tempdir().unwrap();
+    /// let path1 = CString::new(tmp1.path().to_str().unwrap()).unwrap();
+    /// let tmp2 = tempfile::tempdir().unwrap();
+    /// let path2 = CString::new(tmp2.path().to_str().unwrap()).unwrap();
+    ///
+    /// let mut options = Options::new();
+    /// options.set_compression(false);
+    /// options.set_bloom_filter(10);
+    /// options.set_cache_size(16 << 20);
+    /// options.set_env(Env::default_env());
+    ///
+    /// let cloned = options.clone();
+    /// assert_eq!(format!("{:?}", options), format!("{:?}", cloned));
+    ///
+    /// let mut db1 = Database::new();
+    /// db1.open_with(&path1, options).unwrap();
+    /// let mut db2 = Database::new();
+    /// db2.open_with(&path2, cloned).unwrap();
+    /// ```
+    fn clone(&self) -> Self {
+        let mut ret = Self::new();
+        ret.set_create_if_missing(self.create_if_missing);
+        ret.set_compression(self.compression);
+        if let Some(bytes) = self.max_file_size {
+            ret.set_max_file_size(bytes);
+        }
+        if let Some(policy) = self.filter_policy.as_ref() {
+            ret.set_bloom_filter(policy.bits_per_key());
+        }
+        if let Some(cache) = self.cache.as_ref() {
+            ret.set_cache(cache.clone());
+        }
+        if self.env.is_some() {
+            // The C API of LevelDB provides only the default environment.
+            ret.set_env(Env::default_env());
+        }
+        ret
+    }
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -80,12 +193,236 @@ impl Options {
             leveldb_options_set_error_if_exists(ptr, FALSE);
             leveldb_options_set_paranoid_checks(ptr, TRUE);
 
-            Self(NonNull::new_unchecked(ptr))
+            Self {
+                ptr: NonNull::new_unchecked(ptr),
+                env: None,
+                cache: None,
+                filter_policy: None,
+                create_if_missing: true,
+                compression: true,
+                max_file_size: None,
+            }
+        }
+    }
+
+    /// Sets `env` as the environment which the database uses to access the file system and so on.
+    ///
+    /// `self` owns `env` and the environment set before is dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::{Database, Env, Options};
+    /// use std::ffi::CString;
+    /// use tempfile;
+    ///
+    /// let tmp = tempfile::tempdir().unwrap();
+    /// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+    ///
+    /// let mut options = Options::new();
+    /// options.set_env(Env::default_env());
+    ///
+    /// let mut db = Database::new();
+    /// db.open_with(&path, options).unwrap();
+    /// ```
+    pub fn set_env(&mut self, env: Env) {
+        unsafe { leveldb_options_set_env(self.ptr.as_ptr(), env.as_ptr()) };
+        self.env = Some(env);
+    }
+
+    /// Sets whether to create the database if it is missing on open.
+    ///
+    /// The default value is `true` .
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::{Database, Options};
+    /// use std::ffi::CString;
+    /// use tempfile;
+    ///
+    /// let tmp = tempfile::tempdir().unwrap();
+    /// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+    ///
+    /// let mut options = Options::new();
+    /// options.set_create_if_missing(false);
+    ///
+    /// let mut db = Database::new();
+    /// assert!(db.open_with(&path, options).is_err());
+    /// ```
+    pub fn set_create_if_missing(&mut self, create_if_missing: bool) {
+        let val = create_if_missing as c_uchar;
+        unsafe { leveldb_options_set_create_if_missing(self.ptr.as_ptr(), val) };
+        self.create_if_missing = create_if_missing;
+    }
+
+    /// Sets LRU cache holding `capacity` bytes at most to cache the uncompressed blocks.
+    ///
+    /// LevelDB uses 8 MB internal cache by default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::{Database, Options};
+    /// use std::ffi::CString;
+    /// use tempfile;
+    ///
+    /// let tmp = tempfile::tempdir().unwrap();
+    /// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+    ///
+    /// let mut options = Options::new();
+    /// options.set_cache_size(16 << 20);
+    ///
+    /// let mut db = Database::new();
+    /// db.open_with(&path, options).unwrap();
+    /// ```
+    pub fn set_cache_size(&mut self, capacity: usize) {
+        self.set_cache(Cache::new_lru(capacity));
+    }
+
+    /// Sets `cache` to cache the uncompressed blocks.
+    ///
+    /// The databases opened with the clones of the same [`Cache`] share the cache.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::{Cache, Database, Options};
+    /// use std::ffi::CString;
+    /// use tempfile;
+    ///
+    /// let tmp1 = tempfile::tempdir().unwrap();
+    /// let path1 = CString::new(tmp1.path().to_str().unwrap()).unwrap();
+    /// let tmp2 = tempfile::tempdir().unwrap();
+    /// let path2 = CString::new(tmp2.path().to_str().unwrap()).unwrap();
+    ///
+    /// let cache = Cache::new_lru(16 << 20);
+    ///
+    /// let mut options1 = Options::new();
+    /// options1.set_cache(cache.clone());
+    /// let mut options2 = Options::new();
+    /// options2.set_cache(cache);
+    ///
+    /// let mut db1 = Database::new();
+    /// db1.open_with(&path1, options1).unwrap();
+    /// let mut db2 = Database::new();
+    /// db2.open_with(&path2, options2).unwrap();
+    /// ```
+    pub fn set_cache(&mut self, cache: Cache) {
+        unsafe { leveldb_options_set_cache(self.ptr.as_ptr(), cache.as_ptr()) };
+        self.cache = Some(cache);
+    }
+
+    /// Sets bloom filter using `bits_per_key` bits for each key to reduce the disk reads.
+    ///
+    /// 10 is a good value for `bits_per_key` , which yields about 1% false positive rate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::{Database, Options};
+    /// use std::ffi::CString;
+    /// use tempfile;
+    ///
+    /// let tmp = tempfile::tempdir().unwrap();
+    /// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+    ///
+    /// let mut options = Options::new();
+    /// options.set_bloom_filter(10);
+    ///
+    /// let mut db = Database::new();
+    /// db.open_with(&path, options).unwrap();
+    /// ```
+    pub fn set_bloom_filter(&mut self, bits_per_key: c_int) {
+        let policy = FilterPolicy::new_bloom(bits_per_key);
+        unsafe { leveldb_options_set_filter_policy(self.ptr.as_ptr(), policy.as_ptr()) };
+        self.filter_policy = Some(policy);
+    }
+
+    /// Sets whether to compress the blocks with Snappy.
+    ///
+    /// The default value is `true` . Note that LevelDB stores the blocks uncompressed if it is
+    /// built without Snappy.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::{Database, Options};
+    /// use std::ffi::CString;
+    /// use tempfile;
+    ///
+    /// let tmp = tempfile::tempdir().unwrap();
+    /// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+    ///
+    /// let mut options = Options::new();
+    /// options.set_compression(false);
+    ///
+    /// let mut db = Database::new();
+    /// db.open_with(&path, options).unwrap();
+    /// ```
+    pub fn set_compression(&mut self, compression: bool) {
+        let val = if compression {
+            Compression::Snappy
+        } else {
+            Compression::No
+        };
+        unsafe { leveldb_options_set_compression(self.ptr.as_ptr(), val) };
+        self.compression = compression;
+    }
+
+    /// Sets the size of the table file LevelDB creates by the compaction to about `bytes` bytes.
+    ///
+    /// The larger files reduce the number of the files to open; the smaller files make each
+    /// compaction faster. The default is 2 MB. LevelDB clamps `bytes` to the range from 1 MB to
+    /// 1 GB.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::{Database, Options, WriteBatch};
+    /// use std::ffi::CString;
+    /// use std::fs;
+    /// use tempfile;
+    ///
+    /// let tmp = tempfile::tempdir().unwrap();
+    /// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+    ///
+    /// let mut options = Options::new();
+    /// options.set_max_file_size(1 << 20);
+    /// options.set_compression(false);
+    ///
+    /// let mut db = Database::new();
+    /// db.open_with(&path, options).unwrap();
+    ///
+    /// let value = vec![0xff; 64 << 10];
+    /// for i in 0..256_u32 {
+    ///     let mut batch = WriteBatch::new();
+    ///     batch.put(&i.to_be_bytes(), &value);
+    ///     mouse_leveldb::write(&db, &mut batch).unwrap();
+    /// }
+    /// db.close();
+    ///
+    /// let tables = fs::read_dir(tmp.path())
+    ///     .unwrap()
+    ///     .filter(|entry| {
+    ///         let file = entry.as_ref().unwrap().path();
+    ///         file.extension().map(|ext| ext == "ldb").unwrap_or(false)
+    ///     })
+    ///     .count();
+    /// assert!(2 <= tables);
+    /// ```
+    pub fn set_max_file_size(&mut self, bytes: usize) {
+        // LevelDB exports it, however, 'leveldb_sys' does not declare it.
+        extern "C" {
+            fn leveldb_options_set_max_file_size(options: *mut leveldb_options_t, size: usize);
         }
+
+        unsafe { leveldb_options_set_max_file_size(self.ptr.as_ptr(), bytes) };
+        self.max_file_size = Some(bytes);
     }
 
     /// Provides a raw pointer to wrapped address.
     pub fn as_ptr(&self) -> *const leveldb_options_t {
-        self.0.as_ptr()
+        self.ptr.as_ptr()
     }
 }
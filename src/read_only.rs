@@ -0,0 +1,158 @@
+// Copyright 2021 Shin Yoshida
+//
+// "LGPL-3.0-or-later OR Apache-2.0 OR BSD-2-Clause"
+//
+// This is part of mouse-leveldb
+//
+//  mouse-leveldb is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU Lesser General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  mouse-leveldb is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU Lesser General Public License for more details.
+//
+//  You should have received a copy of the GNU Lesser General Public License
+//  along with mouse-leveldb.  If not, see <http://www.gnu.org/licenses/>.
+//
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted
+// provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of
+//    conditions and the following disclaimer.
+// 2. Redistributions in binary form must reproduce the above copyright notice, this
+//    list of conditions and the following disclaimer in the documentation and/or other
+//    materials provided with the distribution.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+// ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+// WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+// IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT,
+// INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+// NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+
+use crate::database::Database;
+use crate::error::Result;
+use crate::iter::Iter;
+use crate::octets::Octets;
+use std::collections::{BTreeMap, HashMap};
+
+/// `ReadOnly` is a view of [`Database`] which provides only the methods not to modify the
+/// database.
+///
+/// `ReadOnly` restricts the operations at the type level; it shares the database with the
+/// original [`Database`] .
+///
+/// It is not allowed to write or to close the database via `ReadOnly` .
+///
+/// ```compile_fail
+/// use mouse_leveldb::{Database, WriteBatch};
+///
+/// let db = Database::new();
+/// let view = db.read_only_view();
+///
+/// let mut batch = WriteBatch::new();
+/// mouse_leveldb::write(&view, &mut batch).unwrap();
+/// ```
+///
+/// ```compile_fail
+/// use mouse_leveldb::Database;
+///
+/// let db = Database::new();
+/// let mut view = db.read_only_view();
+/// view.close();
+/// ```
+#[derive(Clone, Copy)]
+pub struct ReadOnly<'a>(&'a Database);
+
+/// Creates a new instance wrapping `db` .
+pub fn new(db: &Database) -> ReadOnly<'_> {
+    ReadOnly(db)
+}
+
+impl<'a> ReadOnly<'a> {
+    /// Same to [`crate::get`] .
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::{Database, WriteBatch};
+    /// use std::ffi::CString;
+    /// use tempfile;
+    ///
+    /// let tmp = tempfile::tempdir().unwrap();
+    /// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+    ///
+    /// let mut db = Database::new();
+    /// db.open(&path).unwrap();
+    ///
+    /// let key: &[u8] = &[1, 2, 3];
+    /// let value: &[u8] = &[4, 5];
+    ///
+    /// let mut batch = WriteBatch::new();
+    /// batch.put(key, value);
+    /// mouse_leveldb::write(&db, &mut batch).unwrap();
+    ///
+    /// let view = db.read_only_view();
+    /// assert_eq!(value, view.get(key).unwrap().as_ref());
+    /// assert_eq!(1, view.count().unwrap());
+    ///
+    /// let mut it = view.iter();
+    /// it.seek_to_first();
+    /// assert_eq!(Some(key), it.key());
+    /// ```
+    #[inline]
+    pub fn get(&self, key: &[u8]) -> Result<Octets> {
+        crate::get(self.0, key)
+    }
+
+    /// Same to [`crate::iter`] .
+    #[inline]
+    pub fn iter(&self) -> Iter<'a> {
+        crate::iter(self.0)
+    }
+
+    /// Same to [`crate::count`] .
+    #[inline]
+    pub fn count(&self) -> Result<u64> {
+        crate::count(self.0)
+    }
+
+    /// Same to [`Database::estimate_total_size`] .
+    #[inline]
+    pub fn estimate_total_size(&self) -> Result<u64> {
+        self.0.estimate_total_size()
+    }
+
+    /// Same to [`Database::to_btree_map`] .
+    #[inline]
+    pub fn to_btree_map(&self) -> Result<BTreeMap<Vec<u8>, Vec<u8>>> {
+        self.0.to_btree_map()
+    }
+
+    /// Same to [`Database::to_hash_map`] .
+    #[inline]
+    pub fn to_hash_map(&self) -> Result<HashMap<Vec<u8>, Vec<u8>>> {
+        self.0.to_hash_map()
+    }
+}
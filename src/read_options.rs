@@ -51,25 +51,38 @@
 // ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
 // POSSIBILITY OF SUCH DAMAGE.
 
+use crate::snapshot::Snapshot;
+use core::marker::PhantomData;
 use core::ptr::NonNull;
 use leveldb_sys::*;
 use std::os::raw::c_uchar;
 
 /// `ReadOptions` is a wrapper of `*mut leveldb_readoptions_t` to make sure to destruct on the
 /// drop.
-pub struct ReadOptions(NonNull<leveldb_readoptions_t>);
+///
+/// Lifetime `'a` is that of the [`Snapshot`] set to `self` if any.
+pub struct ReadOptions<'a>(NonNull<leveldb_readoptions_t>, PhantomData<&'a ()>);
 
-unsafe impl Send for ReadOptions {}
-unsafe impl Sync for ReadOptions {}
+unsafe impl Send for ReadOptions<'_> {}
+unsafe impl Sync for ReadOptions<'_> {}
 
-impl Drop for ReadOptions {
+impl Drop for ReadOptions<'_> {
     fn drop(&mut self) {
         unsafe { leveldb_readoptions_destroy(self.0.as_ptr()) };
     }
 }
 
-impl ReadOptions {
+impl Default for ReadOptions<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> ReadOptions<'a> {
     /// Creates a new instance.
+    ///
+    /// By default, the read operations with `self` see the latest state, do not fill the block
+    /// cache, and do not verify the checksums.
     pub fn new() -> Self {
         unsafe {
             let ptr = leveldb_readoptions_create();
@@ -79,10 +92,32 @@ impl ReadOptions {
             leveldb_readoptions_set_fill_cache(ptr, FALSE);
             leveldb_readoptions_set_verify_checksums(ptr, FALSE);
 
-            Self(NonNull::new_unchecked(ptr))
+            Self(NonNull::new_unchecked(ptr), PhantomData)
         }
     }
 
+    /// Makes the read operations with `self` see the state when `snapshot` was created.
+    pub fn set_snapshot(&mut self, snapshot: &'a Snapshot<'_>) {
+        unsafe { leveldb_readoptions_set_snapshot(self.0.as_ptr(), snapshot.as_ptr()) };
+    }
+
+    /// Makes the read operations with `self` cache the blocks read from the disk if `fill_cache` is
+    /// `true` . The default value is `false` .
+    ///
+    /// It is reasonable to keep `false` for a large scan not to evict the other data from the
+    /// cache.
+    pub fn set_fill_cache(&mut self, fill_cache: bool) {
+        let val = fill_cache as c_uchar;
+        unsafe { leveldb_readoptions_set_fill_cache(self.0.as_ptr(), val) };
+    }
+
+    /// Makes the read operations with `self` verify the checksums of all the data read from the
+    /// disk if `verify_checksums` is `true` . The default value is `false` .
+    pub fn set_verify_checksums(&mut self, verify_checksums: bool) {
+        let val = verify_checksums as c_uchar;
+        unsafe { leveldb_readoptions_set_verify_checksums(self.0.as_ptr(), val) };
+    }
+
     /// Provides a raw pointer to the wrapped address.
     #[inline]
     pub fn as_ptr(&self) -> *const leveldb_readoptions_t {
@@ -0,0 +1,112 @@
+// Copyright 2021 Shin Yoshida
+//
+// "LGPL-3.0-or-later OR Apache-2.0 OR BSD-2-Clause"
+//
+// This is part of mouse-leveldb
+//
+//  mouse-leveldb is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU Lesser General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  mouse-leveldb is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU Lesser General Public License for more details.
+//
+//  You should have received a copy of the GNU Lesser General Public License
+//  along with mouse-leveldb.  If not, see <http://www.gnu.org/licenses/>.
+//
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted
+// provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of
+//    conditions and the following disclaimer.
+// 2. Redistributions in binary form must reproduce the above copyright notice, this
+//    list of conditions and the following disclaimer in the documentation and/or other
+//    materials provided with the distribution.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+// ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+// WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+// IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT,
+// INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+// NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+
+use crate::database::Database;
+use crate::error::Result;
+use crate::write_batch::WriteBatch;
+use crate::write_options::WriteOptions;
+
+/// `ScopedDb` is a view of [`Database`] which writes with the [`WriteOptions`] given to
+/// [`Database::with_write_options`] .
+///
+/// `ScopedDb` shares the database with the original [`Database`] .
+#[derive(Clone, Copy)]
+pub struct ScopedDb<'a> {
+    db: &'a Database,
+    options: &'a WriteOptions,
+}
+
+/// Creates a new instance writing to `db` with `options` .
+pub fn new<'a>(db: &'a Database, options: &'a WriteOptions) -> ScopedDb<'a> {
+    ScopedDb { db, options }
+}
+
+impl<'a> ScopedDb<'a> {
+    /// Puts `key` and `value` into the database.
+    ///
+    /// It is an error if the database is not opened.
+    #[inline]
+    pub fn put(&self, key: &[u8], value: &[u8]) -> Result<()> {
+        let mut batch = WriteBatch::new();
+        batch.put(key, value);
+        self.write(&mut batch)
+    }
+
+    /// Deletes `key` from the database.
+    ///
+    /// It is an error if the database is not opened.
+    #[inline]
+    pub fn delete(&self, key: &[u8]) -> Result<()> {
+        let mut batch = WriteBatch::new();
+        batch.delete(key);
+        self.write(&mut batch)
+    }
+
+    /// Same to [`crate::write_with_options`] .
+    #[inline]
+    pub fn write(&self, batch: &mut WriteBatch) -> Result<()> {
+        crate::write_with_options(self.db, batch, self.options)
+    }
+
+    /// Returns the database `self` writes to.
+    #[inline]
+    pub fn database(&self) -> &'a Database {
+        self.db
+    }
+
+    /// Returns the options `self` writes with.
+    #[inline]
+    pub fn write_options(&self) -> &'a WriteOptions {
+        self.options
+    }
+}
@@ -0,0 +1,236 @@
+// Copyright 2021 Shin Yoshida
+//
+// "LGPL-3.0-or-later OR Apache-2.0 OR BSD-2-Clause"
+//
+// This is part of mouse-leveldb
+//
+//  mouse-leveldb is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU Lesser General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  mouse-leveldb is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU Lesser General Public License for more details.
+//
+//  You should have received a copy of the GNU Lesser General Public License
+//  along with mouse-leveldb.  If not, see <http://www.gnu.org/licenses/>.
+//
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted
+// provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of
+//    conditions and the following disclaimer.
+// 2. Redistributions in binary form must reproduce the above copyright notice, this
+//    list of conditions and the following disclaimer in the documentation and/or other
+//    materials provided with the distribution.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+// ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+// WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+// IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT,
+// INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+// NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+
+use crate::octets::{self, Octets};
+use core::ops::{Bound, Deref, RangeBounds};
+use std::fmt;
+use std::sync::Arc;
+
+/// `SharedOctets` is a view of a range in [`Octets`] sharing the ownership of the buffer.
+///
+/// `SharedOctets` is created by [`Octets::into_shared`] . Cloning or slicing `SharedOctets` does
+/// not copy the bytes; the buffer is freed when the last view is dropped.
+///
+/// User can access the bytes in the range via the `Deref` implementation.
+///
+/// # Examples
+///
+/// ```
+/// use mouse_leveldb::Octets;
+///
+/// let shared = Octets::from(b"headbodytail".to_vec()).into_shared();
+/// let head = shared.slice(..4);
+/// let body = shared.slice(4..8);
+/// let tail = shared.slice(8..);
+/// drop(shared);
+///
+/// assert_eq!(b"head", head.as_ref());
+/// assert_eq!(b"body", body.as_ref());
+/// assert_eq!(b"tail", tail.as_ref());
+///
+/// // Slicing is relative to the view.
+/// assert_eq!(b"od", body.slice(1..=2).as_ref());
+/// ```
+///
+/// The buffer LevelDB allocated is freed exactly once after all the views are dropped.
+/// ( `live_octets` is available only if feature `testing` is enabled.)
+///
+/// ```
+/// # #[cfg(feature = "testing")]
+/// # {
+/// use mouse_leveldb::{TestDb, WriteBatch};
+/// use std::thread;
+///
+/// let db = TestDb::new().unwrap();
+///
+/// let mut batch = WriteBatch::new();
+/// batch.put(b"key", b"aaabbbccc");
+/// mouse_leveldb::write(&db, &mut batch).unwrap();
+///
+/// let before = mouse_leveldb::live_octets();
+///
+/// let shared = mouse_leveldb::get(&db, b"key").unwrap().into_shared();
+/// let views = [shared.slice(..3), shared.slice(3..6), shared.slice(6..)];
+/// drop(shared);
+/// assert_eq!(before + 1, mouse_leveldb::live_octets());
+///
+/// let [a, b, c] = views;
+/// drop(b);
+/// assert_eq!(b"aaa", a.as_ref());
+/// assert_eq!(before + 1, mouse_leveldb::live_octets());
+///
+/// let c = thread::spawn(move || {
+///     assert_eq!(b"ccc", c.as_ref());
+///     c
+/// })
+/// .join()
+/// .unwrap();
+///
+/// drop(a);
+/// assert_eq!(before + 1, mouse_leveldb::live_octets());
+/// drop(c);
+/// assert_eq!(before, mouse_leveldb::live_octets());
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct SharedOctets {
+    octets: Arc<Octets>,
+    start: usize,
+    end: usize,
+}
+
+/// Creates a new instance viewing the whole of `octets` .
+pub fn new(octets: Octets) -> SharedOctets {
+    let end = octets.len();
+    SharedOctets {
+        octets: Arc::new(octets),
+        start: 0,
+        end,
+    }
+}
+
+impl SharedOctets {
+    /// Creates a new view of `range` in `self` sharing the buffer with `self` .
+    ///
+    /// `range` is relative to `self` , not to the original [`Octets`] .
+    ///
+    /// # Panics
+    ///
+    /// Causes a panic if `range` is out of `self` , as the slice indexing does.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::Octets;
+    /// use std::panic;
+    ///
+    /// let shared = Octets::from(vec![1, 2, 3]).into_shared();
+    /// assert_eq!(&[2, 3], shared.slice(1..).as_ref());
+    /// assert_eq!(true, shared.slice(3..).is_empty());
+    ///
+    /// assert!(panic::catch_unwind(|| shared.slice(2..4)).is_err());
+    /// assert!(panic::catch_unwind(|| shared.slice(2..1)).is_err());
+    /// ```
+    pub fn slice<R>(&self, range: R) -> Self
+    where
+        R: RangeBounds<usize>,
+    {
+        let bounds = (range.start_bound().cloned(), range.end_bound().cloned());
+        let bytes = &self.deref()[bounds];
+
+        // The indexing above has checked the overflow.
+        let start = match bounds.0 {
+            Bound::Included(n) => n,
+            Bound::Excluded(n) => n + 1,
+            Bound::Unbounded => 0,
+        };
+
+        Self {
+            octets: self.octets.clone(),
+            start: self.start + start,
+            end: self.start + start + bytes.len(),
+        }
+    }
+}
+
+impl fmt::Debug for SharedOctets {
+    /// Shows the length and the first [`Octets::DEBUG_PREVIEW_LEN`] bytes in the range as
+    /// `Debug` of [`Octets`] does.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::Octets;
+    ///
+    /// let shared = Octets::from(vec![0x01, 0xab, b'z']).into_shared();
+    /// assert_eq!(
+    ///     r#"SharedOctets { len: 2, bytes: b"\xabz" }"#,
+    ///     format!("{:?}", shared.slice(1..))
+    /// );
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        octets::debug_fmt(f, "SharedOctets", self.deref())
+    }
+}
+
+impl Deref for SharedOctets {
+    type Target = [u8];
+
+    #[inline]
+    fn deref(&self) -> &[u8] {
+        &self.octets[self.start..self.end]
+    }
+}
+
+impl AsRef<[u8]> for SharedOctets {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        self.deref()
+    }
+}
+
+impl PartialEq<Self> for SharedOctets {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.deref() == other.deref()
+    }
+}
+
+impl Eq for SharedOctets {}
+
+impl PartialEq<[u8]> for SharedOctets {
+    #[inline]
+    fn eq(&self, other: &[u8]) -> bool {
+        self.deref() == other
+    }
+}
@@ -0,0 +1,184 @@
+// Copyright 2021 Shin Yoshida
+//
+// "LGPL-3.0-or-later OR Apache-2.0 OR BSD-2-Clause"
+//
+// This is part of mouse-leveldb
+//
+//  mouse-leveldb is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU Lesser General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  mouse-leveldb is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU Lesser General Public License for more details.
+//
+//  You should have received a copy of the GNU Lesser General Public License
+//  along with mouse-leveldb.  If not, see <http://www.gnu.org/licenses/>.
+//
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted
+// provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of
+//    conditions and the following disclaimer.
+// 2. Redistributions in binary form must reproduce the above copyright notice, this
+//    list of conditions and the following disclaimer in the documentation and/or other
+//    materials provided with the distribution.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+// ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+// WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+// IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT,
+// INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+// NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+
+use crate::database::{self, Database};
+use crate::error::{self, Result};
+use crate::iter::{self, Iter};
+use core::iter::FusedIterator;
+
+/// `SizedIter` iterates the (key, value) pairs in a range, knowing how many pairs are left.
+///
+/// `SizedIter` implements `ExactSizeIterator` ; the item is the pair of the copied key and value.
+/// It iterates a [`Snapshot`] created by [`SizedIter::new`] , so the writes after that do not
+/// change the number of the pairs.
+///
+/// # Warnings
+///
+/// [`SizedIter::new`] scans all the pairs in the range to count them before iterating, so it takes
+/// O(N) time upfront. It is intended for tests and benchmarks.
+///
+/// [`Snapshot`]: crate::Snapshot
+pub struct SizedIter<'a> {
+    it: Iter<'a>,
+    len: usize,
+    end: Vec<u8>,
+}
+
+impl<'a> SizedIter<'a> {
+    /// Creates a new instance iterating the pairs whose key is in the range `[start, end)` .
+    ///
+    /// `end` is exclusive, however, the empty `end` means no upper bound as
+    /// [`crate::delete_range`] does.
+    ///
+    /// It is an error if `db` is not opened.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::{Database, ErrorKind, SizedIter, WriteBatch};
+    /// use std::ffi::CString;
+    /// use tempfile;
+    ///
+    /// let tmp = tempfile::tempdir().unwrap();
+    /// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+    ///
+    /// let mut db = Database::new();
+    /// assert_eq!(ErrorKind::NotOpened, SizedIter::new(&db, &[], &[]).err().unwrap().kind());
+    ///
+    /// db.open(&path).unwrap();
+    ///
+    /// let mut batch = WriteBatch::new();
+    /// for i in 1..=5 {
+    ///     batch.put(&[i], &[i * 10]);
+    /// }
+    /// mouse_leveldb::write(&db, &mut batch).unwrap();
+    ///
+    /// let mut it = SizedIter::new(&db, &[2], &[5]).unwrap();
+    /// assert_eq!(3, it.len());
+    ///
+    /// // Writing after created does not change the pairs to iterate.
+    /// batch.put(&[3, 0], &[]);
+    /// mouse_leveldb::write(&db, &mut batch).unwrap();
+    ///
+    /// assert_eq!(Some((vec![2], vec![20])), it.next());
+    /// assert_eq!(2, it.len());
+    /// assert_eq!(vec![vec![3], vec![4]], it.map(|(k, _)| k).collect::<Vec<_>>());
+    ///
+    /// assert_eq!(6, SizedIter::new(&db, &[], &[]).unwrap().len());
+    /// assert_eq!(0, SizedIter::new(&db, &[4], &[4]).unwrap().len());
+    /// ```
+    pub fn new(db: &'a Database, start: &[u8], end: &[u8]) -> Result<Self> {
+        if database::as_ptr(db).is_none() {
+            return Err(error::not_opened());
+        }
+
+        let mut it = iter::with_snapshot(db);
+        let in_range = |key: &[u8]| end.is_empty() || key < end;
+
+        let mut len = 0;
+        it.seek(start);
+        while it.key().is_some_and(in_range) {
+            len += 1;
+            it.seek_next();
+        }
+        it.status()?;
+
+        it.seek(start);
+        Ok(Self {
+            it,
+            len,
+            end: end.to_vec(),
+        })
+    }
+
+    /// Returns the error if the underlying [`Iter`] has encountered any; otherwise returns `Ok` .
+    ///
+    /// `self` stops iterating after encountering an error, even if `len` is not 0.
+    #[inline]
+    pub fn status(&self) -> Result<()> {
+        self.it.status()
+    }
+}
+
+impl Iterator for SizedIter<'_> {
+    type Item = (Vec<u8>, Vec<u8>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+
+        let ret = match (self.it.key(), self.it.value()) {
+            (Some(key), Some(value)) if self.end.is_empty() || key < self.end.as_slice() => {
+                (key.to_vec(), value.to_vec())
+            }
+            _ => {
+                self.len = 0;
+                return None;
+            }
+        };
+
+        self.len -= 1;
+        self.it.seek_next();
+        Some(ret)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl ExactSizeIterator for SizedIter<'_> {}
+
+impl FusedIterator for SizedIter<'_> {}
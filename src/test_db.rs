@@ -0,0 +1,127 @@
+// Copyright 2021 Shin Yoshida
+//
+// "LGPL-3.0-or-later OR Apache-2.0 OR BSD-2-Clause"
+//
+// This is part of mouse-leveldb
+//
+//  mouse-leveldb is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU Lesser General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  mouse-leveldb is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU Lesser General Public License for more details.
+//
+//  You should have received a copy of the GNU Lesser General Public License
+//  along with mouse-leveldb.  If not, see <http://www.gnu.org/licenses/>.
+//
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted
+// provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of
+//    conditions and the following disclaimer.
+// 2. Redistributions in binary form must reproduce the above copyright notice, this
+//    list of conditions and the following disclaimer in the documentation and/or other
+//    materials provided with the distribution.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+// ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+// WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+// IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT,
+// INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+// NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+
+use crate::database::Database;
+use crate::error::{self, Result};
+use core::ops::{Deref, DerefMut};
+use std::ffi::CString;
+use std::io;
+use std::path::Path;
+use tempfile::TempDir;
+
+/// `TestDb` is a [`Database`] opened in a new temporary directory, which is removed on the drop.
+///
+/// This is available only if feature `testing` is enabled.
+///
+/// User can access the wrapped [`Database`] via the `Deref` and `DerefMut` implementation.
+///
+/// # Examples
+///
+/// ```
+/// use mouse_leveldb::{TestDb, WriteBatch};
+///
+/// let db = TestDb::new().unwrap();
+/// let dir = db.dir().to_path_buf();
+///
+/// let mut batch = WriteBatch::new();
+/// batch.put(&[1], &[10]);
+/// mouse_leveldb::write(&db, &mut batch).unwrap();
+/// assert_eq!(&[10], mouse_leveldb::get(&db, &[1]).unwrap().as_ref());
+///
+/// assert_eq!(true, dir.exists());
+/// drop(db);
+/// assert_eq!(false, dir.exists());
+/// ```
+pub struct TestDb {
+    // 'db' must be closed before 'dir' is removed.
+    db: Database,
+    dir: TempDir,
+}
+
+impl TestDb {
+    /// Creates a new temporary directory and opens a new database there.
+    pub fn new() -> Result<Self> {
+        let dir = tempfile::tempdir().map_err(error::io)?;
+        let path = dir.path().to_str().ok_or_else(|| {
+            let e = io::Error::new(io::ErrorKind::InvalidData, "non UTF-8 temporary path");
+            error::io(e)
+        })?;
+        let path = CString::new(path).map_err(|e| error::io(e.into()))?;
+
+        let mut db = Database::new();
+        db.open(&path)?;
+        Ok(Self { db, dir })
+    }
+
+    /// Returns the path to the temporary directory where the database files are stored.
+    #[inline]
+    pub fn dir(&self) -> &Path {
+        self.dir.path()
+    }
+}
+
+impl Deref for TestDb {
+    type Target = Database;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.db
+    }
+}
+
+impl DerefMut for TestDb {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.db
+    }
+}
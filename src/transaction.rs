@@ -0,0 +1,184 @@
+// Copyright 2021 Shin Yoshida
+//
+// "LGPL-3.0-or-later OR Apache-2.0 OR BSD-2-Clause"
+//
+// This is part of mouse-leveldb
+//
+//  mouse-leveldb is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU Lesser General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  mouse-leveldb is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU Lesser General Public License for more details.
+//
+//  You should have received a copy of the GNU Lesser General Public License
+//  along with mouse-leveldb.  If not, see <http://www.gnu.org/licenses/>.
+//
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted
+// provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of
+//    conditions and the following disclaimer.
+// 2. Redistributions in binary form must reproduce the above copyright notice, this
+//    list of conditions and the following disclaimer in the documentation and/or other
+//    materials provided with the distribution.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+// ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+// WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+// IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT,
+// INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+// NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+
+use crate::database::Database;
+use crate::error::Result;
+use crate::write_batch::WriteBatch;
+use crate::write_options::WriteOptions;
+
+/// `Transaction` bundles a [`WriteBatch`] and the [`WriteOptions`] to commit it with.
+///
+/// The operations are applied atomically on [`commit`] ; either all of them or none of them are
+/// written.
+///
+/// # Warnings
+///
+/// LevelDB does not provide the read isolation. The reads while building `Transaction` do not
+/// see the operations which are not committed yet, and the other writers can modify the keys
+/// before `Transaction` is committed. Use [`Snapshot`] to read a consistent view.
+///
+/// There is nothing like `fill_cache` for the writes; it is a read option. See
+/// [`ReadOptions::set_fill_cache`] .
+///
+/// # Examples
+///
+/// ```
+/// use mouse_leveldb::{Database, Transaction};
+/// use std::ffi::CString;
+/// use tempfile;
+///
+/// let tmp = tempfile::tempdir().unwrap();
+/// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+///
+/// let mut db = Database::new();
+/// db.open(&path).unwrap();
+///
+/// // Commits without waiting for the data to reach the disk; the default is to wait.
+/// let mut tx = Transaction::new();
+/// tx.set_sync(false);
+/// tx.put(&[1], &[10]);
+/// tx.put(&[2], &[20]);
+/// tx.delete(&[1]);
+/// assert_eq!(3, tx.len());
+///
+/// // Not written until committed.
+/// assert_eq!(0, mouse_leveldb::get(&db, &[2]).unwrap().len());
+///
+/// tx.commit(&db).unwrap();
+/// assert_eq!(0, tx.len());
+///
+/// assert_eq!(0, mouse_leveldb::get(&db, &[1]).unwrap().len());
+/// assert_eq!(&[20], mouse_leveldb::get(&db, &[2]).unwrap().as_ref());
+/// ```
+///
+/// [`commit`]: Self::commit
+/// [`Snapshot`]: crate::Snapshot
+/// [`ReadOptions::set_fill_cache`]: crate::ReadOptions::set_fill_cache
+pub struct Transaction {
+    batch: WriteBatch,
+    options: WriteOptions,
+}
+
+impl Default for Transaction {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Transaction {
+    /// Creates a new empty instance with the default [`WriteOptions`] .
+    #[inline]
+    pub fn new() -> Self {
+        Self::with_options(WriteOptions::new())
+    }
+
+    /// Creates a new empty instance to commit with `options` .
+    #[inline]
+    pub fn with_options(options: WriteOptions) -> Self {
+        Self {
+            batch: WriteBatch::new(),
+            options,
+        }
+    }
+
+    /// Sets whether to flush the data to the disk before [`commit`] returns.
+    ///
+    /// The default value is `true` ; see [`WriteOptions::set_sync`] for details.
+    ///
+    /// [`commit`]: Self::commit
+    #[inline]
+    pub fn set_sync(&mut self, sync: bool) {
+        self.options.set_sync(sync);
+    }
+
+    /// Appends the operation to put `key` and `value` .
+    #[inline]
+    pub fn put(&mut self, key: &[u8], value: &[u8]) {
+        self.batch.put(key, value);
+    }
+
+    /// Appends the operation to delete `key` .
+    #[inline]
+    pub fn delete(&mut self, key: &[u8]) {
+        self.batch.delete(key);
+    }
+
+    /// Returns the number of the operations not committed yet.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.batch.len()
+    }
+
+    /// Returns `true` if no operation is waiting to be committed.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Writes all the operations to `db` atomically.
+    ///
+    /// `self` is cleared on success; if failed, `self` is left as it is so that the caller can
+    /// retry it.
+    ///
+    /// It is an error if `db` is not opened.
+    #[inline]
+    pub fn commit(&mut self, db: &Database) -> Result<()> {
+        crate::write_with_options(db, &mut self.batch, &self.options)
+    }
+
+    /// Discards all the operations not committed yet.
+    #[inline]
+    pub fn rollback(&mut self) {
+        self.batch.clear();
+    }
+}
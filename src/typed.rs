@@ -0,0 +1,224 @@
+// Copyright 2021 Shin Yoshida
+//
+// "LGPL-3.0-or-later OR Apache-2.0 OR BSD-2-Clause"
+//
+// This is part of mouse-leveldb
+//
+//  mouse-leveldb is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU Lesser General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  mouse-leveldb is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU Lesser General Public License for more details.
+//
+//  You should have received a copy of the GNU Lesser General Public License
+//  along with mouse-leveldb.  If not, see <http://www.gnu.org/licenses/>.
+//
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted
+// provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of
+//    conditions and the following disclaimer.
+// 2. Redistributions in binary form must reproduce the above copyright notice, this
+//    list of conditions and the following disclaimer in the documentation and/or other
+//    materials provided with the distribution.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+// ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+// WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+// IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT,
+// INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+// NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+
+use crate::database::Database;
+use crate::error::{self, Error, Result};
+use crate::write_batch::WriteBatch;
+use core::marker::PhantomData;
+use serde_crate::de::DeserializeOwned;
+use serde_crate::Serialize;
+
+/// `Codec` converts the keys and the values of [`TypedDatabase`] into bytes and back.
+pub trait Codec {
+    /// Error type to be returned on failure.
+    type Error: std::error::Error + Send + Sync + 'static;
+
+    /// Serializes `value` into bytes.
+    fn encode<T>(value: &T) -> core::result::Result<Vec<u8>, Self::Error>
+    where
+        T: ?Sized + Serialize;
+
+    /// Deserializes `bytes` into a new instance of `T` .
+    fn decode<T>(bytes: &[u8]) -> core::result::Result<T, Self::Error>
+    where
+        T: DeserializeOwned;
+}
+
+/// `Bincode` is the default [`Codec`] using crate `bincode` .
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Bincode;
+
+impl Codec for Bincode {
+    type Error = bincode::Error;
+
+    #[inline]
+    fn encode<T>(value: &T) -> core::result::Result<Vec<u8>, Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        bincode::serialize(value)
+    }
+
+    #[inline]
+    fn decode<T>(bytes: &[u8]) -> core::result::Result<T, Self::Error>
+    where
+        T: DeserializeOwned,
+    {
+        bincode::deserialize(bytes)
+    }
+}
+
+/// `TypedDatabase` wraps [`Database`] to store `K` and `V` serialized by `C` instead of the raw
+/// bytes.
+///
+/// This is available only if feature `serde` is enabled.
+///
+/// # Warnings
+///
+/// The order of the keys in the database is the order of the serialized bytes, which is not
+/// always the same as the order of `K` .
+pub struct TypedDatabase<K, V, C = Bincode> {
+    db: Database,
+    _phantom: PhantomData<(K, V, C)>,
+}
+
+impl<K, V, C> TypedDatabase<K, V, C>
+where
+    K: Serialize,
+    V: Serialize + DeserializeOwned,
+    C: Codec,
+{
+    /// Creates a new instance wrapping `db` .
+    ///
+    /// `db` is not required to be opened yet.
+    #[inline]
+    pub fn new(db: Database) -> Self {
+        Self {
+            db,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Provides a reference to the wrapped [`Database`] .
+    #[inline]
+    pub fn database(&self) -> &Database {
+        &self.db
+    }
+
+    /// Provides a mutable reference to the wrapped [`Database`] .
+    #[inline]
+    pub fn database_mut(&mut self) -> &mut Database {
+        &mut self.db
+    }
+
+    /// Consumes `self` and returns the wrapped [`Database`] .
+    #[inline]
+    pub fn into_inner(self) -> Database {
+        self.db
+    }
+
+    /// Returns the value corresponding to `key` , or `None` if `key` is not stored.
+    ///
+    /// It is an error if the wrapped database is not opened.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::{Database, TypedDatabase};
+    /// use std::ffi::CString;
+    /// use tempfile;
+    ///
+    /// let tmp = tempfile::tempdir().unwrap();
+    /// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+    ///
+    /// let mut db = TypedDatabase::<u32, String>::new(Database::new());
+    /// db.database_mut().open(&path).unwrap();
+    ///
+    /// assert_eq!(None, db.get(&1).unwrap());
+    ///
+    /// db.put(&1, &"one".to_string()).unwrap();
+    /// assert_eq!(Some("one".to_string()), db.get(&1).unwrap());
+    /// ```
+    pub fn get(&self, key: &K) -> Result<Option<V>> {
+        let key = encode::<C, K>(key)?;
+        match crate::get_with_options(&self.db, &key, &crate::READ_OPTIONS)? {
+            None => Ok(None),
+            Some(bytes) => C::decode(&bytes).map(Some).map_err(to_error),
+        }
+    }
+
+    /// Stores `value` as the value corresponding to `key` .
+    ///
+    /// It is an error if the wrapped database is not opened.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::{Database, TypedDatabase};
+    /// use std::ffi::CString;
+    /// use tempfile;
+    ///
+    /// let tmp = tempfile::tempdir().unwrap();
+    /// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+    ///
+    /// let mut db = TypedDatabase::<String, Vec<u8>>::new(Database::new());
+    /// db.database_mut().open(&path).unwrap();
+    ///
+    /// db.put(&"key".to_string(), &vec![1, 2, 3]).unwrap();
+    /// db.put(&"key".to_string(), &vec![4]).unwrap();
+    /// assert_eq!(Some(vec![4]), db.get(&"key".to_string()).unwrap());
+    /// ```
+    pub fn put(&self, key: &K, value: &V) -> Result<()> {
+        let key = encode::<C, K>(key)?;
+        let value = encode::<C, V>(value)?;
+
+        let mut batch = WriteBatch::new();
+        batch.put(&key, &value);
+        crate::write(&self.db, &mut batch)
+    }
+}
+
+fn encode<C, T>(value: &T) -> Result<Vec<u8>>
+where
+    C: Codec,
+    T: ?Sized + Serialize,
+{
+    C::encode(value).map_err(to_error)
+}
+
+fn to_error<E>(e: E) -> Error
+where
+    E: std::error::Error + Send + Sync + 'static,
+{
+    error::codec(Box::new(e))
+}
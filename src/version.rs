@@ -0,0 +1,138 @@
+// Copyright 2021 Shin Yoshida
+//
+// "LGPL-3.0-or-later OR Apache-2.0 OR BSD-2-Clause"
+//
+// This is part of mouse-leveldb
+//
+//  mouse-leveldb is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU Lesser General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  mouse-leveldb is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU Lesser General Public License for more details.
+//
+//  You should have received a copy of the GNU Lesser General Public License
+//  along with mouse-leveldb.  If not, see <http://www.gnu.org/licenses/>.
+//
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted
+// provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of
+//    conditions and the following disclaimer.
+// 2. Redistributions in binary form must reproduce the above copyright notice, this
+//    list of conditions and the following disclaimer in the documentation and/or other
+//    materials provided with the distribution.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+// ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+// WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+// IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT,
+// INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+// NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+
+use leveldb_sys::{leveldb_major_version, leveldb_minor_version};
+use once_cell::sync::Lazy;
+use std::fmt;
+
+/// The major version of LevelDB linked to this crate.
+///
+/// The value is taken from the linked library on the first access, so it is correct even if
+/// another LevelDB library is linked instead of the one crate `leveldb_sys` bundles. This is the
+/// same to the major version [`version`] returns.
+///
+/// # Examples
+///
+/// ```
+/// use mouse_leveldb::{Version, LEVELDB_MAJOR_VERSION, LEVELDB_MINOR_VERSION};
+///
+/// assert!(1 <= *LEVELDB_MAJOR_VERSION);
+///
+/// let version = Version::linked();
+/// assert_eq!(*LEVELDB_MAJOR_VERSION, version.major);
+/// assert_eq!(*LEVELDB_MINOR_VERSION, version.minor);
+/// ```
+pub static LEVELDB_MAJOR_VERSION: Lazy<i32> = Lazy::new(|| unsafe { leveldb_major_version() });
+
+/// The minor version of LevelDB linked to this crate.
+///
+/// See [`LEVELDB_MAJOR_VERSION`] for details.
+pub static LEVELDB_MINOR_VERSION: Lazy<i32> = Lazy::new(|| unsafe { leveldb_minor_version() });
+
+/// `Version` represents the version of LevelDB.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Version {
+    /// The major version.
+    pub major: i32,
+    /// The minor version.
+    pub minor: i32,
+}
+
+impl Version {
+    /// Returns the version of LevelDB linked to this crate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::Version;
+    ///
+    /// let version = Version::linked();
+    /// assert_eq!((version.major, version.minor), mouse_leveldb::version());
+    /// assert_eq!(format!("{}.{}", version.major, version.minor), version.to_string());
+    /// ```
+    pub fn linked() -> Self {
+        let (major, minor) = version();
+        Self { major, minor }
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
+
+/// Returns the (major, minor) version of LevelDB linked to this crate.
+///
+/// # Examples
+///
+/// ```
+/// let (major, _minor) = mouse_leveldb::version();
+/// assert!(1 <= major);
+/// ```
+pub fn version() -> (i32, i32) {
+    unsafe { (leveldb_major_version(), leveldb_minor_version()) }
+}
+
+/// Same to [`version`] .
+///
+/// # Examples
+///
+/// ```
+/// let (major, _minor) = mouse_leveldb::leveldb_version();
+/// assert!(1 <= major);
+/// ```
+#[inline]
+pub fn leveldb_version() -> (i32, i32) {
+    version()
+}
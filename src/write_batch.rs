@@ -51,11 +51,33 @@
 // ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
 // POSSIBILITY OF SUCH DAMAGE.
 
+use crate::database::{self, Database};
+use crate::error::{self, Result};
 use leveldb_sys::{
-    leveldb_writebatch_clear, leveldb_writebatch_create, leveldb_writebatch_destroy,
-    leveldb_writebatch_put, leveldb_writebatch_t,
+    leveldb_writebatch_clear, leveldb_writebatch_create, leveldb_writebatch_delete,
+    leveldb_writebatch_destroy, leveldb_writebatch_iterate, leveldb_writebatch_put,
+    leveldb_writebatch_t,
 };
-use std::os::raw::c_char;
+use std::fmt;
+use std::iter::FromIterator;
+use std::os::raw::{c_char, c_void};
+
+/// `BatchEntry` represents an operation stored in [`WriteBatch`] .
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum BatchEntry {
+    /// Stores `value` as the value corresponding to `key` .
+    Put {
+        /// The key to store.
+        key: Vec<u8>,
+        /// The value to store.
+        value: Vec<u8>,
+    },
+    /// Deletes `key` and the corresponding value.
+    Delete {
+        /// The key to delete.
+        key: Vec<u8>,
+    },
+}
 
 /// `WriteBatch` is a wrapper of `*mut leveldb_writebatch_t` to make sure to destruct on the drop.
 pub struct WriteBatch {
@@ -66,6 +88,91 @@ pub struct WriteBatch {
 unsafe impl Send for WriteBatch {}
 unsafe impl Sync for WriteBatch {}
 
+impl fmt::Debug for WriteBatch {
+    /// Shows how many operations `self` has.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::WriteBatch;
+    ///
+    /// let mut batch = WriteBatch::new();
+    /// batch.put(&[1], &[2]);
+    /// batch.delete(&[3]);
+    ///
+    /// assert_eq!("WriteBatch { len: 2 }", format!("{:?}", batch));
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WriteBatch")
+            .field("len", &self.len_)
+            .finish()
+    }
+}
+
+impl<'a> FromIterator<(&'a [u8], &'a [u8])> for WriteBatch {
+    /// Creates a new instance holding all the (key, value) pairs in `iter` to put.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::{batch_entries, BatchEntry, WriteBatch};
+    ///
+    /// let pairs: &[(&[u8], &[u8])] = &[(&[1], &[10]), (&[2], &[20]), (&[3], &[30])];
+    ///
+    /// let batch: WriteBatch = pairs.iter().copied().filter(|(k, _)| k[0] != 2).collect();
+    /// assert_eq!(2, batch.len());
+    ///
+    /// let entries = batch_entries(&batch);
+    /// assert_eq!(
+    ///     BatchEntry::Put {
+    ///         key: vec![3],
+    ///         value: vec![30]
+    ///     },
+    ///     entries[1]
+    /// );
+    /// ```
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = (&'a [u8], &'a [u8])>,
+    {
+        Self::from_put_iter(iter.into_iter())
+    }
+}
+
+impl<'a> Extend<(&'a [u8], &'a [u8])> for WriteBatch {
+    /// Appends all the (key, value) pairs in `iter` to put.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::{batch_entries, BatchEntry, WriteBatch};
+    ///
+    /// let pairs: &[(&[u8], &[u8])] = &[(&[1], &[10]), (&[2], &[20])];
+    /// let more: &[(&[u8], &[u8])] = &[(&[3], &[30])];
+    ///
+    /// let mut batch = WriteBatch::new();
+    /// batch.delete(&[0]);
+    /// batch.extend(pairs.iter().copied().chain(more.iter().copied()));
+    /// assert_eq!(4, batch.len());
+    ///
+    /// let entries = batch_entries(&batch);
+    /// assert_eq!(BatchEntry::Delete { key: vec![0] }, entries[0]);
+    /// assert_eq!(
+    ///     BatchEntry::Put {
+    ///         key: vec![3],
+    ///         value: vec![30]
+    ///     },
+    ///     entries[3]
+    /// );
+    /// ```
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = (&'a [u8], &'a [u8])>,
+    {
+        self.put_many(iter);
+    }
+}
+
 impl Drop for WriteBatch {
     fn drop(&mut self) {
         if let Some(ptr) = self.ptr {
@@ -88,7 +195,34 @@ impl WriteBatch {
         Self { ptr: None, len_: 0 }
     }
 
-    /// Returns how many (key, value) pairs `self` has.
+    /// Creates a new instance and the wrapped object at once.
+    ///
+    /// [`new`] delays creating the wrapped object until the first operation is appended;
+    /// this method is for the hot loops that know they are going to append operations.
+    ///
+    /// LevelDB has no API to reserve the buffer, so `_capacity` is ignored for now.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::WriteBatch;
+    ///
+    /// let mut batch = WriteBatch::with_capacity(16);
+    /// assert_eq!(true, batch.is_initialized());
+    /// assert_eq!(0, batch.len());
+    ///
+    /// batch.put(&[1], &[10]);
+    /// assert_eq!(1, batch.len());
+    /// ```
+    ///
+    /// [`new`]: Self::new
+    pub fn with_capacity(_capacity: usize) -> Self {
+        let mut ret = Self::new();
+        ret.init();
+        ret
+    }
+
+    /// Returns how many operations `self` has.
     ///
     /// # Examples
     ///
@@ -122,6 +256,38 @@ impl WriteBatch {
         self.len_
     }
 
+    /// Returns `true` if the wrapped object has been created, either by [`with_capacity`] or
+    /// lazily by the first operation; otherwise returns `false` .
+    ///
+    /// [`clear`] does not destroy the wrapped object.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::WriteBatch;
+    ///
+    /// let mut batch = WriteBatch::new();
+    /// assert_eq!(false, batch.is_initialized());
+    ///
+    /// batch.put(&[1], &[10]);
+    /// assert_eq!(true, batch.is_initialized());
+    ///
+    /// batch.clear();
+    /// assert_eq!(true, batch.is_initialized());
+    /// assert_eq!(0, batch.len());
+    ///
+    /// let batch = WriteBatch::with_capacity(16);
+    /// assert_eq!(true, batch.is_initialized());
+    /// assert_eq!(0, batch.len());
+    /// ```
+    ///
+    /// [`with_capacity`]: Self::with_capacity
+    /// [`clear`]: Self::clear
+    #[inline]
+    pub fn is_initialized(&self) -> bool {
+        self.ptr.is_some()
+    }
+
     /// Appends a pair of `(key, value)` to self.
     ///
     /// # Warnings
@@ -152,17 +318,8 @@ impl WriteBatch {
     #[inline]
     pub fn put(&mut self, key: &[u8], value: &[u8]) {
         unsafe {
-            let ptr = match self.ptr {
-                None => {
-                    let ptr = leveldb_writebatch_create();
-                    self.ptr = Some(ptr);
-                    ptr
-                }
-                Some(ptr) => ptr,
-            };
-
             leveldb_writebatch_put(
-                ptr,
+                self.init(),
                 key.as_ptr() as *const c_char,
                 key.len(),
                 value.as_ptr() as *const c_char,
@@ -173,6 +330,251 @@ impl WriteBatch {
         self.len_ += 1;
     }
 
+    /// Appends all the (key, value) pairs in `pairs` to self.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::{Database, WriteBatch};
+    /// use std::ffi::CString;
+    /// use tempfile;
+    ///
+    /// let tmp = tempfile::tempdir().unwrap();
+    /// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+    ///
+    /// let mut db = Database::new();
+    /// db.open(&path).unwrap();
+    ///
+    /// let pairs = vec![(vec![1], vec![10]), (vec![2], vec![20]), (vec![3], vec![])];
+    ///
+    /// let mut batch = WriteBatch::new();
+    /// batch.put_many(pairs.iter().map(|(k, v)| (k, v)));
+    /// assert_eq!(3, batch.len());
+    ///
+    /// mouse_leveldb::write(&db, &mut batch).unwrap();
+    /// for (k, v) in pairs.iter() {
+    ///     assert_eq!(v.as_slice(), mouse_leveldb::get(&db, k).unwrap().as_ref());
+    /// }
+    /// ```
+    pub fn put_many<I, K, V>(&mut self, pairs: I)
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<[u8]>,
+        V: AsRef<[u8]>,
+    {
+        for (k, v) in pairs {
+            self.put(k.as_ref(), v.as_ref());
+        }
+    }
+
+    /// Creates a new instance holding all the (key, value) pairs in `iter` to put.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::{Database, WriteBatch};
+    /// use std::collections::HashMap;
+    /// use std::ffi::CString;
+    /// use tempfile;
+    ///
+    /// let tmp = tempfile::tempdir().unwrap();
+    /// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+    ///
+    /// let mut db = Database::new();
+    /// db.open(&path).unwrap();
+    ///
+    /// let mut map = HashMap::new();
+    /// map.insert(vec![1], vec![10]);
+    /// map.insert(vec![2], vec![20]);
+    ///
+    /// let mut batch = WriteBatch::from_put_iter(map.iter());
+    /// assert_eq!(2, batch.len());
+    ///
+    /// mouse_leveldb::write(&db, &mut batch).unwrap();
+    /// for (k, v) in map.iter() {
+    ///     assert_eq!(v.as_slice(), mouse_leveldb::get(&db, k).unwrap().as_ref());
+    /// }
+    /// ```
+    pub fn from_put_iter<I, K, V>(iter: I) -> Self
+    where
+        I: Iterator<Item = (K, V)>,
+        K: AsRef<[u8]>,
+        V: AsRef<[u8]>,
+    {
+        let mut ret = Self::new();
+        ret.put_many(iter);
+        ret
+    }
+
+    /// Appends an operation to delete `key` to self.
+    ///
+    /// It is not an error to delete the key that is not stored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::{Database, WriteBatch};
+    /// use std::ffi::CString;
+    /// use tempfile;
+    ///
+    /// let tmp = tempfile::tempdir().unwrap();
+    /// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+    ///
+    /// let mut db = Database::new();
+    /// db.open(&path).unwrap();
+    ///
+    /// let key: &[u8] = &[1, 2, 3];
+    /// let value: &[u8] = &[4, 5];
+    ///
+    /// let mut batch = WriteBatch::new();
+    /// batch.put(key, value);
+    /// mouse_leveldb::write(&db, &mut batch).unwrap();
+    ///
+    /// batch.delete(key);
+    /// assert_eq!(1, batch.len());
+    /// mouse_leveldb::write(&db, &mut batch).unwrap();
+    ///
+    /// assert_eq!(&[] as &[u8], mouse_leveldb::get(&db, key).unwrap().as_ref());
+    /// ```
+    #[inline]
+    pub fn delete(&mut self, key: &[u8]) {
+        unsafe { leveldb_writebatch_delete(self.init(), key.as_ptr() as *const c_char, key.len()) };
+        self.len_ += 1;
+    }
+
+    /// Always returns an error because LevelDB does not support column families. Use [`put`]
+    /// instead.
+    ///
+    /// This method exists only to tell the users familiar with RocksDB; nothing is appended to
+    /// `self` . The kind of the error is [`ErrorKind::NotSupported`] .
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::{ErrorKind, WriteBatch};
+    ///
+    /// let mut batch = WriteBatch::new();
+    /// let e = batch.put_cf("default", &[1], &[10]).unwrap_err();
+    /// assert_eq!(ErrorKind::NotSupported, e.kind());
+    /// assert!(e.to_string().contains("WriteBatch::put"));
+    /// assert_eq!(0, batch.len());
+    /// ```
+    ///
+    /// [`put`]: Self::put
+    /// [`ErrorKind::NotSupported`]: crate::ErrorKind::NotSupported
+    pub fn put_cf(&mut self, _cf: &str, _key: &[u8], _value: &[u8]) -> Result<()> {
+        Err(error::not_supported(
+            "LevelDB does not support column families; use WriteBatch::put instead",
+        ))
+    }
+
+    /// Always returns an error because LevelDB does not support column families. Use [`delete`]
+    /// instead.
+    ///
+    /// This method exists only to tell the users familiar with RocksDB; nothing is appended to
+    /// `self` . The kind of the error is [`ErrorKind::NotSupported`] .
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::{ErrorKind, WriteBatch};
+    ///
+    /// let mut batch = WriteBatch::new();
+    /// let e = batch.delete_cf("default", &[1]).unwrap_err();
+    /// assert_eq!(ErrorKind::NotSupported, e.kind());
+    /// assert!(e.to_string().contains("WriteBatch::delete"));
+    /// assert_eq!(0, batch.len());
+    /// ```
+    ///
+    /// [`delete`]: Self::delete
+    /// [`ErrorKind::NotSupported`]: crate::ErrorKind::NotSupported
+    pub fn delete_cf(&mut self, _cf: &str, _key: &[u8]) -> Result<()> {
+        Err(error::not_supported(
+            "LevelDB does not support column families; use WriteBatch::delete instead",
+        ))
+    }
+
+    /// Appends the deletes of all the keys stored in `db` in `[start_key, end_key)` and returns
+    /// how many deletes were appended.
+    ///
+    /// `end_key` is exclusive, however, the empty `end_key` means no upper bound.
+    /// If an error occurs while iterating `db` , the deletes appended so far remain in `self` .
+    ///
+    /// LevelDB has no native range delete. This method iterates `db` and appends a delete per key
+    /// instead. Unlike a native range delete, the keys are decided when this method is called;
+    /// the keys written into the range after that are not deleted by `self` .
+    ///
+    /// It is an error if `db` is not opened.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::{Database, WriteBatch};
+    /// use std::ffi::CString;
+    /// use tempfile;
+    ///
+    /// let tmp = tempfile::tempdir().unwrap();
+    /// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+    ///
+    /// let mut db = Database::new();
+    /// db.open(&path).unwrap();
+    ///
+    /// let mut batch = WriteBatch::new();
+    /// for i in 0..10_u8 {
+    ///     batch.put(&[i], &[i]);
+    /// }
+    /// mouse_leveldb::write(&db, &mut batch).unwrap();
+    ///
+    /// assert_eq!(3, batch.delete_range(&db, &[3], &[6]).unwrap());
+    /// batch.put(&[20], &[20]);
+    /// mouse_leveldb::write(&db, &mut batch).unwrap();
+    ///
+    /// assert_eq!(8, mouse_leveldb::count(&db).unwrap());
+    /// assert_eq!(&[2], mouse_leveldb::get(&db, &[2]).unwrap().as_ref());
+    /// assert_eq!(0, mouse_leveldb::get(&db, &[4]).unwrap().len());
+    /// assert_eq!(&[6], mouse_leveldb::get(&db, &[6]).unwrap().as_ref());
+    /// ```
+    pub fn delete_range(
+        &mut self,
+        db: &Database,
+        start_key: &[u8],
+        end_key: &[u8],
+    ) -> Result<usize> {
+        if database::as_ptr(db).is_none() {
+            return Err(error::not_opened());
+        }
+
+        let len = self.len_;
+
+        let mut it = crate::iter(db);
+        it.seek(start_key);
+        while let Some(key) = it.key() {
+            if !end_key.is_empty() && end_key <= key {
+                break;
+            }
+            self.delete(key);
+            it.seek_next();
+        }
+        it.status()?;
+
+        Ok(self.len_ - len)
+    }
+
+    /// Creates the wrapped object if not created yet, and returns the pointer to it.
+    fn init(&mut self) -> *mut leveldb_writebatch_t {
+        match self.ptr {
+            None => {
+                let ptr = unsafe { leveldb_writebatch_create() };
+                // 'leveldb_writebatch_create' aborts rather than returns null on the allocation
+                // failure.
+                debug_assert!(!ptr.is_null());
+                self.ptr = Some(ptr);
+                ptr
+            }
+            Some(ptr) => ptr,
+        }
+    }
+
     /// Deletes the holding keys and values.
     ///
     /// # Examples
@@ -199,7 +601,142 @@ impl WriteBatch {
     }
 }
 
+/// Returns the operations stored in `batch` in the order they were appended.
+///
+/// # Examples
+///
+/// ```
+/// use mouse_leveldb::{BatchEntry, WriteBatch};
+///
+/// let mut batch = WriteBatch::new();
+/// assert_eq!(Vec::<BatchEntry>::new(), mouse_leveldb::batch_entries(&batch));
+///
+/// batch.put(&[1, 2], &[3]);
+/// batch.delete(&[4]);
+///
+/// let expected = vec![
+///     BatchEntry::Put {
+///         key: vec![1, 2],
+///         value: vec![3],
+///     },
+///     BatchEntry::Delete { key: vec![4] },
+/// ];
+/// assert_eq!(expected, mouse_leveldb::batch_entries(&batch));
+/// ```
+pub fn batch_entries(batch: &WriteBatch) -> Vec<BatchEntry> {
+    let mut entries: Vec<BatchEntry> = Vec::with_capacity(batch.len());
+
+    if let Some(ptr) = batch.ptr {
+        unsafe {
+            leveldb_writebatch_iterate(
+                ptr,
+                &mut entries as *mut Vec<BatchEntry> as *mut c_void,
+                put_entry,
+                delete_entry,
+            );
+        }
+    }
+
+    entries
+}
+
+extern "C" fn put_entry(
+    state: *mut c_void,
+    key: *const c_char,
+    keylen: usize,
+    value: *const c_char,
+    vallen: usize,
+) {
+    unsafe {
+        let entries = &mut *(state as *mut Vec<BatchEntry>);
+        entries.push(BatchEntry::Put {
+            key: to_vec(key, keylen),
+            value: to_vec(value, vallen),
+        });
+    }
+}
+
+extern "C" fn delete_entry(state: *mut c_void, key: *const c_char, keylen: usize) {
+    unsafe {
+        let entries = &mut *(state as *mut Vec<BatchEntry>);
+        entries.push(BatchEntry::Delete {
+            key: to_vec(key, keylen),
+        });
+    }
+}
+
+unsafe fn to_vec(ptr: *const c_char, len: usize) -> Vec<u8> {
+    if len == 0 {
+        Vec::new()
+    } else {
+        core::slice::from_raw_parts(ptr as *const u8, len).to_vec()
+    }
+}
+
 /// Returns a pointer to the wrapped address.
-pub fn as_ptr(batch: &mut WriteBatch) -> Option<*mut leveldb_writebatch_t> {
+pub fn as_ptr(batch: &WriteBatch) -> Option<*mut leveldb_writebatch_t> {
     batch.ptr
 }
+
+/// Creates a [`WriteBatch`] holding the `put` and `delete` operations in the order.
+///
+/// With the leading `into db;` clause, writes the batch into `db` by [`crate::write`] at once and
+/// returns the result instead of the batch.
+///
+/// # Examples
+///
+/// ```
+/// use mouse_leveldb::{batch, batch_entries, BatchEntry, Database};
+/// use std::ffi::CString;
+/// use tempfile;
+///
+/// let batch = batch! {
+///     put(b"k1", b"v1");
+///     delete(b"k2");
+///     put(b"k3", b"v3");
+/// };
+/// assert_eq!(3, batch.len());
+/// assert_eq!(BatchEntry::Delete { key: b"k2".to_vec() }, batch_entries(&batch)[1]);
+///
+/// let tmp = tempfile::tempdir().unwrap();
+/// let path = CString::new(tmp.path().to_str().unwrap()).unwrap();
+///
+/// let mut db = Database::new();
+/// db.open(&path).unwrap();
+///
+/// let key: &[u8] = &[1];
+/// batch! {
+///     into db;
+///     put(key, &[10]);
+///     put(&[2], &[20]);
+/// }
+/// .unwrap();
+/// assert_eq!(&[10], mouse_leveldb::get(&db, key).unwrap().as_ref());
+///
+/// batch!(into &db; delete(key)).unwrap();
+/// assert_eq!(true, mouse_leveldb::get(&db, key).unwrap().is_empty());
+/// assert_eq!(&[20], mouse_leveldb::get(&db, &[2]).unwrap().as_ref());
+///
+/// assert_eq!(0, batch! {}.len());
+/// ```
+#[macro_export]
+macro_rules! batch {
+    (@ops $batch:ident;) => {};
+    (@ops $batch:ident; put($key:expr, $value:expr $(,)?) $(; $($rest:tt)*)?) => {
+        $batch.put($key, $value);
+        $crate::batch!(@ops $batch; $($($rest)*)?);
+    };
+    (@ops $batch:ident; delete($key:expr $(,)?) $(; $($rest:tt)*)?) => {
+        $batch.delete($key);
+        $crate::batch!(@ops $batch; $($($rest)*)?);
+    };
+    (into $db:expr; $($ops:tt)*) => {
+        $crate::write(&$db, &mut $crate::batch!($($ops)*))
+    };
+    ($($ops:tt)*) => {{
+        #[allow(unused_mut)]
+        let mut batch = $crate::WriteBatch::new();
+        $crate::batch!(@ops batch; $($ops)*);
+        batch
+    }};
+}
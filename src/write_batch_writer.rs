@@ -0,0 +1,192 @@
+// Copyright 2021 Shin Yoshida
+//
+// "LGPL-3.0-or-later OR Apache-2.0 OR BSD-2-Clause"
+//
+// This is part of mouse-leveldb
+//
+//  mouse-leveldb is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU Lesser General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  mouse-leveldb is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU Lesser General Public License for more details.
+//
+//  You should have received a copy of the GNU Lesser General Public License
+//  along with mouse-leveldb.  If not, see <http://www.gnu.org/licenses/>.
+//
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted
+// provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of
+//    conditions and the following disclaimer.
+// 2. Redistributions in binary form must reproduce the above copyright notice, this
+//    list of conditions and the following disclaimer in the documentation and/or other
+//    materials provided with the distribution.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+// ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+// WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+// IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT,
+// INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+// NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+
+use crate::write_batch::WriteBatch;
+use core::convert::TryInto;
+use std::io;
+
+/// `WriteBatchWriter` appends (key, value) pairs to [`WriteBatch`] via `std::io::Write` .
+///
+/// The bytes written to `WriteBatchWriter` must be the sequence of the following frame.
+///
+/// 1. The byte length of the key as 4 bytes big endian integer.
+/// 1. The key.
+/// 1. The byte length of the value as 4 bytes big endian integer.
+/// 1. The value.
+///
+/// A frame may be split into multiple writes; `WriteBatchWriter` buffers the bytes until the
+/// frame is completed, and then puts the (key, value) pair into the batch.
+///
+/// # Examples
+///
+/// ```
+/// use mouse_leveldb::{BatchEntry, WriteBatch, WriteBatchWriter};
+/// use std::io::{BufWriter, Write};
+///
+/// let mut batch = WriteBatch::new();
+/// {
+///     let mut writer = BufWriter::new(WriteBatchWriter::new(&mut batch));
+///
+///     // key: [1, 2], value: [3]
+///     writer.write_all(&[0, 0, 0, 2, 1, 2]).unwrap();
+///     writer.write_all(&[0, 0, 0, 1, 3]).unwrap();
+///
+///     // key: [4], value: []
+///     writer.write_all(&[0, 0, 0, 1, 4, 0, 0, 0, 0]).unwrap();
+///
+///     writer.flush().unwrap();
+/// }
+///
+/// let expected = vec![
+///     BatchEntry::Put {
+///         key: vec![1, 2],
+///         value: vec![3],
+///     },
+///     BatchEntry::Put {
+///         key: vec![4],
+///         value: vec![],
+///     },
+/// ];
+/// assert_eq!(expected, mouse_leveldb::batch_entries(&batch));
+/// ```
+pub struct WriteBatchWriter<'a> {
+    batch: &'a mut WriteBatch,
+    buffer: Vec<u8>,
+}
+
+impl<'a> WriteBatchWriter<'a> {
+    /// Creates a new instance appending to `batch` .
+    pub fn new(batch: &'a mut WriteBatch) -> Self {
+        Self {
+            batch,
+            buffer: Vec::new(),
+        }
+    }
+
+    /// Returns `true` if `self` holds the bytes of an incomplete frame.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::{WriteBatch, WriteBatchWriter};
+    /// use std::io::Write;
+    ///
+    /// let mut batch = WriteBatch::new();
+    /// let mut writer = WriteBatchWriter::new(&mut batch);
+    ///
+    /// writer.write_all(&[0, 0, 0, 1, 1, 0, 0]).unwrap();
+    /// assert_eq!(true, writer.has_partial_frame());
+    /// assert!(writer.flush().is_err());
+    ///
+    /// writer.write_all(&[0, 1, 2]).unwrap();
+    /// assert_eq!(false, writer.has_partial_frame());
+    /// assert!(writer.flush().is_ok());
+    /// ```
+    pub fn has_partial_frame(&self) -> bool {
+        !self.buffer.is_empty()
+    }
+
+    /// Puts all the completed frames in the buffer into the batch.
+    fn consume(&mut self) {
+        let mut offset = 0;
+
+        while let Some((key, value, next)) = parse_frame(&self.buffer[offset..]) {
+            self.batch.put(key, value);
+            offset += next;
+        }
+
+        self.buffer.drain(..offset);
+    }
+}
+
+/// Returns the key, the value, and the byte length of the frame at the head of `bytes` if the
+/// frame is completed.
+fn parse_frame(bytes: &[u8]) -> Option<(&[u8], &[u8], usize)> {
+    let (key, rest) = parse_field(bytes)?;
+    let (value, _) = parse_field(rest)?;
+    Some((key, value, 8 + key.len() + value.len()))
+}
+
+fn parse_field(bytes: &[u8]) -> Option<(&[u8], &[u8])> {
+    if bytes.len() < 4 {
+        return None;
+    }
+
+    let len = u32::from_be_bytes(bytes[..4].try_into().unwrap()) as usize;
+    let rest = &bytes[4..];
+    if rest.len() < len {
+        None
+    } else {
+        Some(rest.split_at(len))
+    }
+}
+
+impl io::Write for WriteBatchWriter<'_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.buffer.extend_from_slice(buf);
+        self.consume();
+        Ok(buf.len())
+    }
+
+    /// Fails if `self` holds the bytes of an incomplete frame.
+    fn flush(&mut self) -> io::Result<()> {
+        if self.has_partial_frame() {
+            Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "incomplete frame is left",
+            ))
+        } else {
+            Ok(())
+        }
+    }
+}
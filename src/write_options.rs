@@ -68,6 +68,12 @@ impl Drop for WriteOptions {
     }
 }
 
+impl Default for WriteOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl WriteOptions {
     /// Creates a new instance.
     pub fn new() -> Self {
@@ -82,6 +88,23 @@ impl WriteOptions {
         }
     }
 
+    /// Sets whether each write is flushed from the operating system buffer cache before the
+    /// write is considered complete.
+    ///
+    /// The default value is `true` .
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mouse_leveldb::WriteOptions;
+    ///
+    /// let mut options = WriteOptions::new();
+    /// options.set_sync(false);
+    /// ```
+    pub fn set_sync(&mut self, sync: bool) {
+        unsafe { leveldb_writeoptions_set_sync(self.0.as_ptr(), sync as c_uchar) };
+    }
+
     /// Provides a raw pointer to wrapped address.
     pub fn as_ptr(&self) -> *const leveldb_writeoptions_t {
         self.0.as_ptr()